@@ -1,16 +1,25 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "json")]
 use serde::Serialize;
 
+use crate::{repo::merge_counts, tally::Tally};
+
 /// An author of a git commit.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize))]
 pub struct Author {
     /// Name of the author.
     pub name: String,
     /// Total count of commits by author.
     pub total_commits: usize,
+    /// Count of commits by author containing at least one curse.
+    pub naughty_commits: usize,
     /// Total count of curses used by author.
     pub total_curses: usize,
+    /// Share of the curses attributed to the author, which differs from
+    /// `total_curses` when curses in co-authored commits are split.
+    pub score: f64,
     /// HashMap of all the curses the author used.
     pub curses: HashMap<String, usize>,
 }
@@ -22,7 +31,9 @@ impl Author {
             name: name.into(),
             curses: HashMap::new(),
             total_commits: 0,
+            naughty_commits: 0,
             total_curses: 0,
+            score: 0.0,
         }
     }
 
@@ -36,8 +47,43 @@ impl Author {
             })
     }
 
+    /// Adds the commits and curses of the same author in another repository.
+    pub fn merge(&mut self, other: &Author) {
+        self.total_commits += other.total_commits;
+        self.naughty_commits += other.naughty_commits;
+        self.total_curses += other.total_curses;
+        self.score += other.score;
+        merge_counts(&mut self.curses, &other.curses);
+    }
+
     /// `git-anger-management` knows if you've been naughty or not
     pub fn is_naughty(&self) -> bool {
         !self.curses.is_empty()
     }
 }
+
+impl Tally for Author {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn total_commits(&self) -> usize {
+        self.total_commits
+    }
+
+    fn naughty_commits(&self) -> usize {
+        self.naughty_commits
+    }
+
+    fn total_curses(&self) -> usize {
+        self.total_curses
+    }
+
+    fn score(&self) -> f64 {
+        self.score
+    }
+
+    fn curses(&self) -> &HashMap<String, usize> {
+        &self.curses
+    }
+}
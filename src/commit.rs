@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use encoding_rs::{Encoding, UTF_8};
+use git2::{Commit, Signature};
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+/// The name and email of someone involved in a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Identity {
+    /// Name of the person.
+    pub name: String,
+    /// Email of the person.
+    pub email: String,
+}
+
+impl Identity {
+    /// Create an identity from a git signature by decoding it in the encoding
+    /// of its commit, also returning whether anything had to be replaced.
+    pub fn from_signature(signature: &Signature, encoding: &'static Encoding) -> (Self, bool) {
+        let (name, bad_name) = decode(signature.name_bytes(), encoding);
+        let (email, bad_email) = decode(signature.email_bytes(), encoding);
+        (Identity { name, email }, bad_name || bad_email)
+    }
+}
+
+/// The encoding a commit declares for its message and signatures, which is
+/// UTF-8 unless stated otherwise or when it isn't a known encoding.
+pub fn encoding_of(commit: &Commit) -> &'static Encoding {
+    commit
+        .message_encoding()
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8)
+}
+
+/// Decode text in an encoding, replacing anything that isn't valid with
+/// `U+FFFD`, also returning whether anything had to be replaced.
+pub fn decode(bytes: &[u8], encoding: &'static Encoding) -> (String, bool) {
+    let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+    (text.into_owned(), had_errors)
+}
+
+/// A record of a single commit that contained curses.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct CommitRecord {
+    /// The commit hash.
+    pub id: String,
+    /// Who authored the commit.
+    pub author: Identity,
+    /// Who committed the commit, which differs from the author for rebased,
+    /// cherry-picked or applied patches.
+    pub committer: Identity,
+    /// Names of everyone credited with the curses in the commit.
+    pub credited: Vec<String>,
+    /// Time of the commit in seconds since the Unix epoch.
+    pub time: i64,
+    /// The full commit message.
+    pub message: String,
+    /// HashMap of all the curses used in the commit.
+    pub curses: HashMap<String, usize>,
+}
+
+impl CommitRecord {
+    /// The first line of the commit message.
+    pub fn summary(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+
+    /// The abbreviated commit hash.
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(7)]
+    }
+
+    /// The date of the commit in UTC, like `2020-05-13`.
+    pub fn date(&self) -> String {
+        format_date(self.time)
+    }
+}
+
+/// Format seconds since the Unix epoch as a date in UTC, like `2020-05-13`.
+pub fn format_date(time: i64) -> String {
+    // Howard Hinnant's `civil_from_days`, with eras of 400 years starting on
+    // the 1st of March so leap days end up at the end of each year
+    let days = time.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_589_328_000), "2020-05-13");
+        assert_eq!(format_date(-86_400), "1969-12-31");
+    }
+}
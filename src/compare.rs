@@ -0,0 +1,89 @@
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{repo::Repo, tally::Tally};
+
+/// How a count changed between two repositories or revision ranges.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Delta {
+    /// Name of what is counted, like an author or a curse.
+    pub name: String,
+    /// The count in the first repository.
+    pub before: usize,
+    /// The count in the second repository.
+    pub after: usize,
+}
+
+impl Delta {
+    /// Create a delta between two counts.
+    pub fn new(name: impl Into<String>, before: usize, after: usize) -> Self {
+        Delta {
+            name: name.into(),
+            before,
+            after,
+        }
+    }
+
+    /// How much the count changed, negative when it decreased.
+    pub fn change(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+}
+
+/// A comparison of the curses in two repositories or revision ranges.
+#[derive(Debug)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Comparison {
+    /// Name of the first repository.
+    pub before: String,
+    /// Name of the second repository.
+    pub after: String,
+    /// Change in the total amount of commits.
+    pub total_commits: Delta,
+    /// Change in the total amount of curses.
+    pub total_curses: Delta,
+    /// Curses per commit before and after.
+    pub curses_per_commit: (f64, f64),
+    /// Change in the total curses of every author, sorted by name.
+    pub authors: Vec<Delta>,
+    /// Change in how often every curse was used, sorted by name.
+    pub curses: Vec<Delta>,
+}
+
+impl Comparison {
+    /// Compare two repositories, authors and curses missing in one of them
+    /// are counted as zero.
+    pub fn new(before: &Repo, after: &Repo) -> Self {
+        let authors = |repo: &Repo| -> HashMap<String, usize> {
+            repo.authors
+                .values()
+                .map(|a| (a.name.clone(), a.total_curses))
+                .collect()
+        };
+
+        Comparison {
+            before: before.name.clone(),
+            after: after.name.clone(),
+            total_commits: Delta::new("commits", before.total_commits, after.total_commits),
+            total_curses: Delta::new("curses", before.total_curses, after.total_curses),
+            curses_per_commit: (before.curses_per_commit(), after.curses_per_commit()),
+            authors: deltas(&authors(before), &authors(after)),
+            curses: deltas(&before.curses, &after.curses),
+        }
+    }
+}
+
+/// Pair up the counts in two HashMaps, sorted by name.
+fn deltas(before: &HashMap<String, usize>, after: &HashMap<String, usize>) -> Vec<Delta> {
+    let names: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .map(|name| {
+            let count = |counts: &HashMap<String, usize>| counts.get(name).copied().unwrap_or(0);
+            Delta::new(name.as_str(), count(before), count(after))
+        })
+        .collect()
+}
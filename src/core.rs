@@ -1,4 +1,9 @@
-use crate::words::CURSES_SET;
+use crate::{
+    lexicon::{Entry, lookup},
+    words::CURSES_SET,
+};
+
+const CO_AUTHOR_TRAILER: &str = "co-authored-by:";
 
 /// Cleans a string and returns a list containing the cleaned up words.
 ///
@@ -16,6 +21,73 @@ pub fn naughty_word(word: &str) -> bool {
     CURSES_SET.contains(word)
 }
 
+/// Finds all the curses in a message, ignoring case and any `Co-authored-by`
+/// trailers since names aren't curses.
+pub fn find_curses(message: &str) -> Vec<String> {
+    let message = message
+        .lines()
+        .filter(|line| !is_co_author_trailer(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+
+    split_into_clean_words(&message)
+        .filter(|word| naughty_word(word))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// A word in some text, along with the curse it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// The word as it is written.
+    pub word: &'a str,
+    /// The word as it is matched against the curses.
+    pub normalized: String,
+    /// The curse the word matches, if any.
+    pub curse: Option<Entry>,
+}
+
+/// Splits text into words the same way as commit messages, along with the
+/// curses they match.
+pub fn tokenize(text: &str) -> Vec<Token<'_>> {
+    split_into_clean_words(text)
+        .map(|word| {
+            let normalized = word.to_lowercase();
+            Token {
+                word,
+                curse: lookup(&normalized),
+                normalized,
+            }
+        })
+        .collect()
+}
+
+/// Checks if a line of a commit message is a `Co-authored-by` trailer.
+pub fn is_co_author_trailer(line: &str) -> bool {
+    line.trim_start()
+        .get(..CO_AUTHOR_TRAILER.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(CO_AUTHOR_TRAILER))
+}
+
+/// Finds all the co-authors credited in a commit message via
+/// `Co-authored-by: Name <email>` trailers, returning their names and emails.
+pub fn co_authors(message: &str) -> impl Iterator<Item = (&str, &str)> {
+    message
+        .lines()
+        .filter(|line| is_co_author_trailer(line))
+        .map(|line| {
+            let value = line.trim_start()[CO_AUTHOR_TRAILER.len()..].trim();
+            match (value.find('<'), value.rfind('>')) {
+                (Some(start), Some(end)) if start < end => {
+                    (value[..start].trim(), value[start + 1..end].trim())
+                }
+                _ => (value, ""),
+            }
+        })
+        .filter(|(name, _)| !name.is_empty())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -38,4 +110,32 @@ mod test {
             words.collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_co_authors() {
+        let message = "Fix this damn bug\n\nCo-authored-by: Ola Nordmann <ola@example.com>\nco-authored-by: John Doe\nSigned-off-by: Sondre Nilsen <sondre@example.com>";
+        assert_eq!(
+            vec![("Ola Nordmann", "ola@example.com"), ("John Doe", "")],
+            co_authors(message).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_find_curses() {
+        assert_eq!(
+            find_curses("Fix DAMN bug\n\nshit-for-brains\nCo-authored-by: Dick <dick@example.com>"),
+            ["damn", "shit"]
+        );
+        assert!(find_curses("Add tests").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("What the HELL, man");
+        let normalized: Vec<_> = tokens.iter().map(|t| t.normalized.as_str()).collect();
+        assert_eq!(normalized, ["what", "the", "hell", "man"]);
+        assert_eq!(tokens[2].word, "HELL");
+        assert_eq!(tokens[2].curse.map(|c| c.word), Some("hell"));
+        assert!(tokens[3].curse.is_none());
+    }
 }
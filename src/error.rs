@@ -0,0 +1,59 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+/// All the ways scanning a repository can fail.
+#[derive(Debug)]
+pub enum AngerError {
+    /// The path is not inside a git repository.
+    NotARepository(PathBuf),
+    /// The repository has no commits, so there is no `HEAD` to walk from.
+    EmptyRepository(PathBuf),
+    /// A revision or revision range doesn't exist in the repository.
+    InvalidRevision(String),
+    /// A config file couldn't be parsed.
+    Config(String),
+    /// Reading from the file system failed.
+    Io(io::Error),
+    /// Any other error from git.
+    Git(git2::Error),
+}
+
+impl fmt::Display for AngerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AngerError::NotARepository(path) => {
+                write!(f, "{} is not inside a git repository", path.display())
+            }
+            AngerError::EmptyRepository(path) => {
+                write!(f, "{} has no commits yet", path.display())
+            }
+            AngerError::InvalidRevision(revision) => {
+                write!(f, "could not find the revision {}", revision)
+            }
+            AngerError::Config(message) => write!(f, "invalid config: {}", message),
+            AngerError::Io(e) => write!(f, "{}", e),
+            AngerError::Git(e) => write!(f, "{}", e.message()),
+        }
+    }
+}
+
+impl Error for AngerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AngerError::Io(e) => Some(e),
+            AngerError::Git(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AngerError {
+    fn from(e: io::Error) -> Self {
+        AngerError::Io(e)
+    }
+}
+
+impl From<git2::Error> for AngerError {
+    fn from(e: git2::Error) -> Self {
+        AngerError::Git(e)
+    }
+}
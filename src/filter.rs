@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use globset::{GlobBuilder, GlobMatcher};
+use regex::{Regex, RegexBuilder};
+
+use crate::commit::Identity;
+
+/// Names of well known bots and service accounts that don't use the `[bot]`
+/// suffix.
+const KNOWN_BOTS: &[&str] = &[
+    "dependabot",
+    "dependabot-preview",
+    "renovate",
+    "renovate-bot",
+    "greenkeeper",
+    "snyk-bot",
+    "github-actions",
+    "gitlab-ci",
+    "travis-ci",
+    "semantic-release-bot",
+];
+
+/// A pattern matching the name or email of a person, either a glob like
+/// `*@example.com` or a regular expression wrapped in slashes like
+/// `/^(ola|john)/`.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl Pattern {
+    /// Checks if the pattern matches either the name or email of a person,
+    /// ignoring case.
+    pub fn matches(&self, identity: &Identity) -> bool {
+        match &self.matcher {
+            Matcher::Glob(glob) => glob.is_match(&identity.name) || glob.is_match(&identity.email),
+            Matcher::Regex(regex) => {
+                regex.is_match(&identity.name) || regex.is_match(&identity.email)
+            }
+        }
+    }
+
+    /// The pattern as it was written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let matcher = match s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(regex) => RegexBuilder::new(regex)
+                .case_insensitive(true)
+                .build()
+                .map(Matcher::Regex)
+                .map_err(|e| format!("invalid regex {}: {}", s, e))?,
+            None => GlobBuilder::new(s)
+                .case_insensitive(true)
+                .literal_separator(false)
+                .build()
+                .map(|glob| Matcher::Glob(glob.compile_matcher()))
+                .map_err(|e| format!("invalid pattern {}: {}", s, e))?,
+        };
+
+        Ok(Pattern {
+            source: s.to_owned(),
+            matcher,
+        })
+    }
+}
+
+/// Checks if an identity belongs to a bot or service account, like
+/// `dependabot[bot]` or `noreply@github.com`.
+pub fn is_bot(identity: &Identity) -> bool {
+    let name = identity.name.to_lowercase();
+    let email = identity.email.to_lowercase();
+    let local = email.split('@').next().unwrap_or_default();
+
+    name.ends_with("[bot]")
+        || local.ends_with("[bot]")
+        || matches!(local, "noreply" | "no-reply" | "bot")
+        || KNOWN_BOTS.contains(&name.as_str())
+        || KNOWN_BOTS.contains(&local)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn identity(name: &str, email: &str) -> Identity {
+        Identity {
+            name: name.into(),
+            email: email.into(),
+        }
+    }
+
+    #[test]
+    fn test_is_bot() {
+        assert!(is_bot(&identity(
+            "dependabot[bot]",
+            "49699333+dependabot[bot]@users.noreply.github.com"
+        )));
+        assert!(is_bot(&identity("Renovate", "bot@renovateapp.com")));
+        assert!(is_bot(&identity("GitHub", "noreply@github.com")));
+        assert!(!is_bot(&identity(
+            "Sondre Nilsen",
+            "12345+sondr3@users.noreply.github.com"
+        )));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern: Pattern = "*@ci.example.com".parse().unwrap();
+        assert!(pattern.matches(&identity("Jenkins", "jenkins@CI.example.com")));
+        assert!(!pattern.matches(&identity("John Doe", "john@example.com")));
+
+        let pattern: Pattern = "/^(ola|john) /".parse().unwrap();
+        assert!(pattern.matches(&identity("John Doe", "john@example.com")));
+        assert!(!pattern.matches(&identity("Sondre Nilsen", "sondre@example.com")));
+        assert!("/(/".parse::<Pattern>().is_err());
+    }
+}
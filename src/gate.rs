@@ -0,0 +1,71 @@
+use crate::{repo::Repo, tally::Tally};
+
+/// Limits on the curses in a repository, for failing CI checks.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Thresholds {
+    /// The most curses allowed in total.
+    pub max_curses: Option<usize>,
+    /// The most curses allowed per commit on average.
+    pub max_rate: Option<f64>,
+}
+
+impl Thresholds {
+    /// Whether any limits are set at all.
+    pub fn is_empty(&self) -> bool {
+        self.max_curses.is_none() && self.max_rate.is_none()
+    }
+
+    /// Checks a repository against the limits, describing every limit it
+    /// exceeds.
+    pub fn check(&self, repo: &Repo) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(max) = self.max_curses
+            && repo.total_curses > max
+        {
+            violations.push(format!(
+                "found {} curses, but at most {} are allowed",
+                repo.total_curses, max
+            ));
+        }
+        if let Some(max) = self.max_rate
+            && repo.curses_per_commit() > max
+        {
+            violations.push(format!(
+                "found {:.2} curses per commit, but at most {:.2} are allowed",
+                repo.curses_per_commit(),
+                max
+            ));
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let repo = Repo::from_text("text", "damn\n\nshit happens\n\nclean\n\nfine");
+        assert!(Thresholds::default().check(&repo).is_empty());
+
+        let thresholds = Thresholds {
+            max_curses: Some(2),
+            max_rate: Some(0.5),
+        };
+        assert!(thresholds.check(&repo).is_empty());
+
+        let thresholds = Thresholds {
+            max_curses: Some(0),
+            max_rate: Some(0.25),
+        };
+        assert_eq!(
+            thresholds.check(&repo),
+            [
+                "found 2 curses, but at most 0 are allowed",
+                "found 0.50 curses per commit, but at most 0.25 are allowed"
+            ]
+        );
+    }
+}
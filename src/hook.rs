@@ -0,0 +1,150 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use git2::{Config, ErrorCode, Repository};
+
+use crate::{
+    core::find_curses,
+    error::AngerError,
+    lexicon::{Entry, Severity, lookup},
+};
+
+/// Marks hooks installed by us, so they can safely be replaced.
+const MARKER: &str = "# Installed by git-anger-management";
+
+/// The line `git commit -v` puts between the message and the diff, after the
+/// comment prefix.
+const SCISSORS: &str = " ------------------------ >8 ------------------------";
+
+/// The prefix of the comments git adds to commit messages, from
+/// `core.commentChar` in the repository containing `path` or the global
+/// config. With `auto` git picks a prefix per message, so the default `#` is
+/// assumed.
+pub fn comment_prefix(path: &Path) -> String {
+    let config = Repository::discover(path)
+        .and_then(|repo| repo.config())
+        .or_else(|_| Config::open_default());
+    let prefix = config.and_then(|config| {
+        config
+            .get_string("core.commentString")
+            .or_else(|_| config.get_string("core.commentChar"))
+    });
+    match prefix {
+        Ok(prefix) if !prefix.is_empty() && prefix != "auto" => prefix,
+        _ => "#".to_owned(),
+    }
+}
+
+/// Removes what git removes from a commit message before committing it, the
+/// lines starting with the `comment` prefix and everything below the scissors
+/// line of `git commit -v`.
+pub fn strip_comments(message: &str, comment: &str) -> String {
+    message
+        .lines()
+        .take_while(|line| line.strip_prefix(comment) != Some(SCISSORS))
+        .filter(|line| !line.starts_with(comment))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Finds the curses in a commit message that are at least as severe as
+/// `severity`, ignoring comments starting with `comment`.
+pub fn offending_curses(message: &str, comment: &str, severity: Severity) -> Vec<Entry> {
+    find_curses(&strip_comments(message, comment))
+        .iter()
+        .filter_map(|curse| lookup(curse))
+        .filter(|entry| entry.severity >= severity)
+        .collect()
+}
+
+/// The `commit-msg` hook running `hook` with the given arguments on the
+/// message being committed.
+pub fn script(args: &[&str]) -> String {
+    let mut command = vec!["git", "anger-management", "hook"];
+    command.extend(args);
+    format!("#!/bin/sh\n{}\nexec {} \"$1\"\n", MARKER, command.join(" "))
+}
+
+/// Installs a `commit-msg` hook running `hook` with the given arguments in
+/// the repository containing `path`, respecting `core.hooksPath`. Any hook
+/// that wasn't installed by us is only replaced with `force`.
+pub fn install(path: &Path, args: &[&str], force: bool) -> Result<PathBuf, AngerError> {
+    let repo = Repository::discover(path).map_err(|e| match e.code() {
+        ErrorCode::NotFound => AngerError::NotARepository(path.to_path_buf()),
+        _ => AngerError::Git(e),
+    })?;
+    let hooks = match repo.config()?.get_path("core.hooksPath") {
+        Ok(hooks) => repo.workdir().unwrap_or(repo.path()).join(hooks),
+        // Linked worktrees share the hooks of the main repository
+        Err(e) if e.code() == ErrorCode::NotFound => repo.commondir().join("hooks"),
+        Err(e) => return Err(e.into()),
+    };
+
+    let hook = hooks.join("commit-msg");
+    if let Ok(existing) = fs::read_to_string(&hook)
+        && !force
+        && !existing.contains(MARKER)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists, use `--force` to replace it",
+                hook.display()
+            ),
+        )
+        .into());
+    }
+
+    fs::create_dir_all(&hooks)?;
+    fs::write(&hook, script(args))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(hook)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_offending_curses() {
+        let message = "Fix damn bug, fuck\n# shit, this is a comment";
+        let words = |severity| -> Vec<&str> {
+            offending_curses(message, "#", severity)
+                .iter()
+                .map(|entry| entry.word)
+                .collect()
+        };
+        assert_eq!(words(Severity::Mild), ["damn", "fuck"]);
+        assert_eq!(words(Severity::Moderate), ["fuck"]);
+        assert!(words(Severity::Severe).is_empty());
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        let message = "Add clean feature\n\n# Please enter the commit message\n# ------------------------ >8 ------------------------\n# Do not modify or remove the line above.\ndiff --git a/lib.rs b/lib.rs\n+// what the hell\n";
+        assert_eq!(strip_comments(message, "#"), "Add clean feature\n");
+        assert!(offending_curses(message, "#", Severity::Mild).is_empty());
+
+        let message = "Add damn feature\n; shit\n; ------------------------ >8 ------------------------\n+hell";
+        assert_eq!(strip_comments(message, ";"), "Add damn feature");
+        assert_eq!(strip_comments(message, "#"), message);
+    }
+
+    #[test]
+    fn test_script() {
+        assert_eq!(
+            script(&["--strict"]),
+            format!(
+                "#!/bin/sh\n{}\nexec git anger-management hook --strict \"$1\"\n",
+                MARKER
+            )
+        );
+    }
+}
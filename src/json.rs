@@ -0,0 +1,143 @@
+use std::{collections::HashMap, error::Error, io, io::Write};
+
+use serde::Serialize;
+
+use crate::{
+    commit::CommitRecord,
+    compare::Comparison,
+    options::GroupBy,
+    repo::Repo,
+    report::Reporter,
+    summary::{Summary, TOP_CURSES},
+    team::Team,
+};
+
+/// The repository summarized by team instead of by author.
+#[derive(Serialize)]
+struct TeamReport<'a> {
+    name: &'a str,
+    total_commits: usize,
+    total_curses: usize,
+    curses: &'a HashMap<String, usize>,
+    teams: &'a HashMap<String, Team>,
+}
+
+/// A repository, the teams in it or its summary, ready to be serialized.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Report<'a> {
+    Authors(&'a Repo),
+    Teams(TeamReport<'a>),
+    Summary(Summary),
+}
+
+impl<'a> Report<'a> {
+    fn new(repo: &'a Repo, reporter: &JsonReporter) -> Self {
+        if reporter.summary {
+            return Report::Summary(Summary::new(repo, reporter.top.unwrap_or(TOP_CURSES)));
+        }
+
+        match reporter.by {
+            GroupBy::Author => Report::Authors(repo),
+            GroupBy::Team => Report::Teams(TeamReport {
+                name: &repo.name,
+                total_commits: repo.total_commits,
+                total_curses: repo.total_curses,
+                curses: &repo.curses,
+                teams: &repo.teams,
+            }),
+        }
+    }
+}
+
+/// Several repositories and all of them merged together.
+#[derive(Serialize)]
+struct MergedReport<'a> {
+    repositories: Vec<Report<'a>>,
+    merged: Report<'a>,
+}
+
+/// Renders repositories as JSON-objects.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonReporter {
+    /// Whether to summarize the repositories per author or per team.
+    pub by: GroupBy,
+    /// Only summarize the repositories as a whole.
+    pub summary: bool,
+    /// List this many of the top curses in summaries.
+    pub top: Option<usize>,
+}
+
+impl Reporter for JsonReporter {
+    fn render(&self, repo: &Repo, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut *out, &Report::new(repo, self))?;
+        out.flush()?;
+
+        Ok(())
+    }
+
+    fn render_comparison(
+        &self,
+        comparison: &Comparison,
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut *out, comparison)?;
+        out.flush()?;
+
+        Ok(())
+    }
+
+    fn render_log(
+        &self,
+        records: &[CommitRecord],
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut *out, records)?;
+        out.flush()?;
+
+        Ok(())
+    }
+
+    /// Render the repositories as a single JSON-object, along with the merged
+    /// repository when there are several of them.
+    fn render_all(
+        &self,
+        repos: &[&Repo],
+        merged: Option<&Repo>,
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let repositories: Vec<_> = repos.iter().map(|repo| Report::new(repo, self)).collect();
+        match merged {
+            None => {
+                for report in repositories {
+                    serde_json::to_writer(&mut *out, &report)?;
+                }
+            }
+            Some(merged) => serde_json::to_writer(
+                &mut *out,
+                &MergedReport {
+                    repositories,
+                    merged: Report::new(merged, self),
+                },
+            )?,
+        }
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Serialize the repositories into a JSON-object and print it, along with
+/// the merged repository when scanning several of them.
+pub fn print_json(
+    repos: &[Repo],
+    merged: Option<&Repo>,
+    by: GroupBy,
+) -> Result<(), Box<dyn Error>> {
+    let repos: Vec<&Repo> = repos.iter().collect();
+    JsonReporter {
+        by,
+        ..JsonReporter::default()
+    }
+    .render_all(&repos, merged, &mut io::stdout())
+}
@@ -0,0 +1,181 @@
+use std::{fmt, str::FromStr};
+
+use phf::{Map, Set};
+
+use crate::{
+    core::split_into_clean_words,
+    words::{CURSES_SET, MILD_SET, SEVERE_SET, SYNONYMS},
+};
+
+/// How bad a curse is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Merely rude, like `damn` or `crap`.
+    Mild,
+    /// Most curses.
+    Moderate,
+    /// Slurs and words for sexual violence.
+    Severe,
+}
+
+impl Severity {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["mild", "moderate", "severe"];
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mild" => Ok(Severity::Mild),
+            "moderate" => Ok(Severity::Moderate),
+            "severe" => Ok(Severity::Severe),
+            _ => Err(format!("unknown severity: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Mild => "mild",
+            Severity::Moderate => "moderate",
+            Severity::Severe => "severe",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A list of curses in a single language.
+#[derive(Debug)]
+pub struct Lexicon {
+    /// Name of the list.
+    pub name: &'static str,
+    /// Language of the curses, as an ISO 639-1 code.
+    pub language: &'static str,
+    /// Where the list comes from.
+    pub source: &'static str,
+    words: &'static Set<&'static str>,
+    synonyms: &'static Map<&'static str, &'static str>,
+}
+
+impl Lexicon {
+    /// Look up a word in the list, ignoring case.
+    pub fn lookup(&'static self, word: &str) -> Option<Entry> {
+        let word = self.words.get_key(&word.to_lowercase())?;
+        Some(Entry {
+            word,
+            severity: severity(word),
+            lexicon: self,
+        })
+    }
+
+    /// All the curses in the list, in no particular order.
+    pub fn entries(&'static self) -> impl Iterator<Item = Entry> {
+        self.words.iter().map(move |word| Entry {
+            word,
+            severity: severity(word),
+            lexicon: self,
+        })
+    }
+}
+
+impl PartialEq for Lexicon {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Lexicon {}
+
+/// Every list of curses that is matched against.
+pub static LEXICONS: &[Lexicon] = &[Lexicon {
+    name: "profanity",
+    language: "en",
+    source: "https://github.com/coffee-and-fun/google-profanity-words",
+    words: &CURSES_SET,
+    synonyms: &SYNONYMS,
+}];
+
+/// A curse in one of the lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// The curse as it is written in the list.
+    pub word: &'static str,
+    /// How bad the curse is.
+    pub severity: Severity,
+    /// The list containing the curse.
+    pub lexicon: &'static Lexicon,
+}
+
+impl Entry {
+    /// Checks if the curse can ever be matched. Messages are split into words
+    /// at anything that isn't a letter, so curses containing spaces, digits or
+    /// punctuation never match.
+    pub fn is_matchable(&self) -> bool {
+        split_into_clean_words(self.word).eq([self.word])
+    }
+
+    /// A milder word to use instead of the curse, if the list has one.
+    pub fn synonym(&self) -> Option<&'static str> {
+        self.lexicon.synonyms.get(self.word).copied()
+    }
+}
+
+/// Look up a word in every list, ignoring case.
+pub fn lookup(word: &str) -> Option<Entry> {
+    LEXICONS.iter().find_map(|lexicon| lexicon.lookup(word))
+}
+
+/// How bad a curse is, curses are moderate unless they are known to be mild
+/// or severe.
+pub fn severity(word: &str) -> Severity {
+    if SEVERE_SET.contains(word) {
+        Severity::Severe
+    } else if MILD_SET.contains(word) {
+        Severity::Mild
+    } else {
+        Severity::Moderate
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let entry = lookup("Damn").unwrap();
+        assert_eq!(entry.word, "damn");
+        assert_eq!(entry.severity, Severity::Mild);
+        assert_eq!(entry.lexicon.language, "en");
+        assert!(entry.is_matchable());
+
+        assert_eq!(lookup("fuck").unwrap().severity, Severity::Moderate);
+        assert!(!lookup("ass-fucker").unwrap().is_matchable());
+        assert!(!lookup("2 girls 1 cup").unwrap().is_matchable());
+        assert!(lookup("commit").is_none());
+    }
+
+    #[test]
+    fn test_severity_lists_are_curses() {
+        for word in MILD_SET.iter().chain(SEVERE_SET.iter()) {
+            assert!(CURSES_SET.contains(word), "{} is not a curse", word);
+        }
+    }
+
+    #[test]
+    fn test_synonyms() {
+        assert_eq!(lookup("damn").unwrap().synonym(), Some("darn"));
+        assert_eq!(lookup("cunt").unwrap().synonym(), None);
+        for (curse, synonym) in SYNONYMS.entries() {
+            assert!(CURSES_SET.contains(curse), "{} is not a curse", curse);
+            assert!(
+                split_into_clean_words(synonym).all(|word| lookup(word).is_none()),
+                "{} is a curse",
+                synonym
+            );
+        }
+    }
+}
@@ -12,18 +12,20 @@
 //!
 //! ## Features:
 //!
-//! **Note:** None of these features are enabled by default, so you have to opt into
-//! it like so:
+//! **Note:** The default `cli` feature enables everything the binary needs, so
+//! turn off the default features and opt into the ones you want like so:
 //!
 //! ```toml
 //! [dependencies]
-//! git-anger-library = { version = "0.8.0", features=["table", "json"] }
+//! git-anger-management = { version = "0.7.0", default-features = false, features = ["table", "json"] }
 //! ```
 //!
-//! - `json`: Enables Serde serialization of the processed data using with the
-//!   `print_json()` method.
+//! - `json`: Enables Serde serialization of the processed data with the
+//!   `json::print_json()` function.
 //! - `table`: Enables pretty printing of the processed data using TabWriter with
-//!   the function `print_table()`.
+//!   the function `table::print_table()`.
+//! - `cli`: Enables both of the above along with the command line parsing used
+//!   by the binary.
 //!
 //! ## License
 //!
@@ -31,8 +33,40 @@
 //!
 /// A git author
 pub mod author;
+/// Records of individual commits
+pub mod commit;
+/// Comparing repositories or revision ranges
+pub mod compare;
 /// Core algorithms and functionality
 pub mod core;
+/// Errors when scanning repositories
+pub mod error;
+/// Filtering of people and bot accounts
+pub mod filter;
+/// Thresholds for failing CI checks
+pub mod gate;
+/// Checking commit messages in a `commit-msg` hook
+pub mod hook;
+/// Printing repositories as JSON
+#[cfg(feature = "json")]
+pub mod json;
+/// The lists of curses and how bad they are
+pub mod lexicon;
+/// Options controlling how repositories are scanned
+pub mod options;
 /// A simplified representation of a git repository
 pub mod repo;
+/// Rendering repositories in different output formats
+pub mod report;
+/// Removing curses from commit messages
+pub mod sanitize;
+/// Summaries of whole repositories
+pub mod summary;
+/// Printing repositories as tables
+#[cfg(feature = "table")]
+pub mod table;
+/// Counting curses for authors and teams
+pub mod tally;
+/// Grouping authors into teams
+pub mod team;
 mod words;
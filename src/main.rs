@@ -1,12 +1,36 @@
-mod output;
+mod serve;
 
-use std::{env, error::Error, path::PathBuf, time::Instant};
+use std::{
+    cmp::Reverse,
+    env,
+    error::Error,
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process,
+};
 
 use console::Term;
-use git_anger_management::repo::Repo;
+use git_anger_management::{
+    compare::Comparison,
+    core::tokenize,
+    error::AngerError,
+    filter::Pattern,
+    gate::Thresholds,
+    hook,
+    json::JsonReporter,
+    lexicon::{Entry, LEXICONS, Lexicon, Severity, lookup},
+    options::{Attribution, GroupBy, Options, Role},
+    repo::Repo,
+    report::Reporter,
+    sanitize::{self, Style},
+    table::{ColorChoice, Layout, SortBy, TableOptions, TableReporter},
+    team::Teams,
+};
 use structopt::{StructOpt, clap::AppSettings};
-
-use crate::output::{print_json, print_table};
+use tabwriter::TabWriter;
 
 #[derive(StructOpt, Debug)]
 #[structopt(
@@ -15,45 +39,657 @@ use crate::output::{print_json, print_table};
     global_settings(&[AppSettings::ColoredHelp])
 )]
 struct Cli {
-    #[structopt(short, long)]
+    #[structopt(short, long, global = true)]
     /// Verbose output
     verbose: bool,
-    #[structopt(short, long)]
-    /// Only display information about repo
-    _repo: bool,
-    #[structopt(short, long)]
+    #[structopt(short, long, global = true)]
     /// Print output as JSON instead of a prettified table
     json: bool,
+    #[structopt(short, long, parse(from_os_str), global = true)]
+    /// Write the output to a file instead of `stdout`
+    output: Option<PathBuf>,
+    #[structopt(long, default_value = "auto", possible_values = ColorChoice::VARIANTS, global = true)]
+    /// When to color the table, `auto` respects `NO_COLOR` and only colors terminals
+    color: ColorChoice,
+    #[structopt(long, default_value = "author", possible_values = Attribution::VARIANTS, global = true)]
+    /// How to credit curses in commits with `Co-authored-by` trailers
+    attribution: Attribution,
+    #[structopt(long, default_value = "author", possible_values = Role::VARIANTS, global = true)]
+    /// Whether to credit the author or the committer of each commit
+    attribute_to: Role,
+    #[structopt(long, global = true)]
+    /// Include commits by bots and service accounts
+    include_bots: bool,
+    #[structopt(long = "bot", number_of_values = 1, global = true)]
+    /// Glob or /regex/ matching names or emails of additional bots to exclude
+    bots: Vec<Pattern>,
+    #[structopt(short, long = "author", number_of_values = 1, global = true)]
+    /// Only include authors whose name or email matches a glob or a /regex/
+    authors: Vec<Pattern>,
+    #[structopt(
+        short = "x",
+        long = "exclude-author",
+        number_of_values = 1,
+        global = true
+    )]
+    /// Exclude authors whose name or email matches a glob or a /regex/
+    exclude_authors: Vec<Pattern>,
+    #[structopt(long, parse(from_os_str), global = true)]
+    /// Config file mapping names, emails or domains to teams
+    teams: Option<PathBuf>,
+    #[structopt(long, default_value = "author", possible_values = GroupBy::VARIANTS, global = true)]
+    /// Display curses per author or per team, which requires `--teams`
+    by: GroupBy,
+    #[structopt(long, global = true)]
+    /// Only scan a revision range like `v1.0..HEAD`
+    range: Option<String>,
+    #[structopt(long, global = true)]
+    /// Scan submodules as separate repositories
+    submodules: bool,
+    #[structopt(long, global = true)]
+    /// Scan submodules and fold their curses into the repository
+    fold_submodules: bool,
+    #[structopt(flatten)]
+    scan: Scan,
+    #[structopt(flatten)]
+    targets: Targets,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// Options for scanning and displaying the curses in repositories, which can
+/// be given before or after the `scan` subcommand.
+#[derive(StructOpt, Debug)]
+struct Scan {
+    #[structopt(short, long, conflicts_with = "author-detail", global = true)]
+    /// Only display a summary of the repository instead of the curses of every author
+    repo: bool,
+    #[structopt(long, default_value = "name", global = true)]
+    /// Sort by name, total, rate, percent, score or a specific curse
+    sort: SortBy,
+    #[structopt(long, global = true)]
+    /// Reverse the sort order
+    reverse: bool,
+    #[structopt(long, global = true)]
+    /// Only show the top N authors and curses
+    top: Option<usize>,
+    #[structopt(long, default_value = "auto", possible_values = Layout::VARIANTS, global = true)]
+    /// Show curses as columns (wide) or rows (transposed), or pick whichever fits the terminal
+    layout: Layout,
+    #[structopt(long, global = true)]
+    /// Fold any columns beyond this many into a single "other" column
+    max_columns: Option<usize>,
+    #[structopt(long, global = true)]
+    /// Show a table per author with only their own curses and first and last naughty commits
+    author_detail: bool,
+    #[structopt(long, conflicts_with = "author-detail", global = true)]
+    /// Scan text from `stdin` instead of repositories, like `git log -z --format=%B`
+    stdin: bool,
+    #[structopt(
+        long = "file",
+        parse(from_os_str),
+        number_of_values = 1,
+        conflicts_with = "author-detail",
+        global = true
+    )]
+    /// Scan the text in a file instead of repositories, every paragraph counts as a message
+    files: Vec<PathBuf>,
+    #[structopt(long, conflicts_with = "max-curses", global = true)]
+    /// Exit with an error if there are any curses at all, for use in CI
+    fail_on_curses: bool,
+    #[structopt(long, global = true)]
+    /// Exit with an error if there are more than this many curses
+    max_curses: Option<usize>,
+    #[structopt(long, global = true)]
+    /// Exit with an error if there are more than this many curses per commit
+    max_rate: Option<f64>,
+}
+
+/// Ways to inspect the lists of curses.
+#[derive(StructOpt, Debug)]
+enum Words {
+    /// Show how text is split into words and which of them are curses
+    Check {
+        /// Text to look for curses in
+        #[structopt(required = true)]
+        text: Vec<String>,
+    },
+    /// List every curse along with its severity, language and list
+    List {
+        #[structopt(long, possible_values = Severity::VARIANTS)]
+        /// Only list curses at least this severe
+        severity: Option<Severity>,
+    },
+    /// Explain whether a word is a curse and why
+    Explain {
+        /// The word to explain
+        word: String,
+    },
+}
+
+/// The repositories to scan.
+#[derive(StructOpt, Debug, Default)]
+struct Targets {
+    #[structopt(short = "R", long)]
+    /// Scan every repository found in the directories and their subdirectories
+    recursive: bool,
     #[structopt(parse(from_os_str))]
-    /// Directory to parse commits from
-    directory: Option<PathBuf>,
+    /// Directories to parse commits from
+    directories: Vec<PathBuf>,
+}
+
+impl Targets {
+    /// The targets given before a subcommand along with those given after it.
+    fn join(mut self, other: Targets) -> Targets {
+        self.recursive |= other.recursive;
+        self.directories.extend(other.directories);
+        self
+    }
+}
+
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Scan repositories for curses, which is the default
+    Scan(Targets),
+    /// Inspect the lists of curses
+    Words(Words),
+    /// List the commits containing curses, newest first
+    Log(Targets),
+    /// Compare the curses in two repositories or two revision ranges
+    Compare {
+        #[structopt(short = "C", long, parse(from_os_str))]
+        /// Repository to look up revision ranges in, defaults to the current directory
+        directory: Option<PathBuf>,
+        /// Repository or revision range, like `v1.0..v2.0`, to compare against
+        before: String,
+        /// Repository or revision range to compare
+        after: String,
+    },
+    /// Warn about curses in a commit message, for use as a `commit-msg` hook
+    Hook {
+        #[structopt(long)]
+        /// Install a `commit-msg` hook running this check in the current repository
+        install: bool,
+        #[structopt(long, requires = "install")]
+        /// Replace an existing `commit-msg` hook
+        force: bool,
+        #[structopt(long)]
+        /// Reject commits with curses instead of warning about them
+        strict: bool,
+        #[structopt(long, default_value = "mild", possible_values = Severity::VARIANTS)]
+        /// Only look for curses at least this severe
+        severity: Severity,
+        #[structopt(
+            parse(from_os_str),
+            required_unless = "install",
+            conflicts_with = "install"
+        )]
+        /// File containing the commit message
+        message_file: Option<PathBuf>,
+    },
+    /// Suggest messages without curses, as a callback for `git filter-repo --message-callback`
+    Sanitize {
+        #[structopt(long, default_value = "mask", possible_values = Style::VARIANTS)]
+        /// Mask curses like `f***`, or replace them with milder synonyms
+        style: Style,
+        #[structopt(flatten)]
+        targets: Targets,
+    },
+    /// Serve the reports as JSON over HTTP, at `/`, `/summary` and `/log`
+    Serve {
+        #[structopt(long, default_value = "127.0.0.1:8000")]
+        /// Address to listen on
+        address: SocketAddr,
+        #[structopt(flatten)]
+        targets: Targets,
+    },
 }
 
-pub fn main() -> Result<(), Box<dyn Error>> {
-    let start = Instant::now();
+pub fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(exit_code(e.as_ref()));
+    }
+}
+
+/// Exit codes for the errors a scan can fail with, following `sysexits.h`,
+/// and `2` when the curses exceed the thresholds for CI checks.
+fn exit_code(error: &(dyn Error + 'static)) -> i32 {
+    match error.downcast_ref::<AngerError>() {
+        Some(AngerError::InvalidRevision(_)) => 64,
+        Some(AngerError::EmptyRepository(_)) => 65,
+        Some(AngerError::NotARepository(_)) => 66,
+        Some(AngerError::Git(_)) => 70,
+        Some(AngerError::Io(_)) => 74,
+        Some(AngerError::Config(_)) => 78,
+        None if error.is::<ThresholdsExceeded>() => 2,
+        // Reading `stdin` or writing the output fails without an `AngerError`
+        None if error.is::<io::Error>() => 74,
+        None => 1,
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
     let opt = Cli::from_args();
-    let path = match opt.directory {
-        Some(directory) => directory,
-        None => env::current_dir()?,
+    let options = Options {
+        attribution: opt.attribution,
+        attribute_to: opt.attribute_to,
+        include_bots: opt.include_bots,
+        bots: opt.bots,
+        authors: opt.authors,
+        exclude_authors: opt.exclude_authors,
+        teams: opt.teams.as_deref().map(Teams::from_file).transpose()?,
+        range: opt.range,
+        submodules: opt.submodules || opt.fold_submodules,
+        fold_submodules: opt.fold_submodules,
     };
+    if opt.by == GroupBy::Team && options.teams.is_none() {
+        return Err("displaying curses by team requires a `--teams` config file".into());
+    }
 
-    let verbose = opt.verbose;
-    let json = opt.json;
+    let mut out: Box<dyn Write> = match &opt.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(with_path(path))?)),
+        None => Box::new(io::stdout()),
+    };
+    let display = &opt.scan;
+    let reporter: Box<dyn Reporter> = if opt.json {
+        Box::new(JsonReporter {
+            by: opt.by,
+            summary: display.repo,
+            top: display.top,
+        })
+    } else {
+        let to_terminal = opt.output.is_none();
+        Box::new(TableReporter {
+            options: TableOptions {
+                by: opt.by,
+                sort_by: display.sort.clone(),
+                reverse: display.reverse,
+                top: display.top,
+                layout: display.layout,
+                max_columns: display.max_columns,
+                colored: match opt.color {
+                    ColorChoice::Auto => to_terminal && opt.color.enabled(),
+                    color => color.enabled(),
+                },
+                width: Term::stdout()
+                    .size_checked()
+                    .filter(|_| to_terminal)
+                    .map(|(_, width)| usize::from(width)),
+                author_detail: display.author_detail,
+                summary: display.repo,
+            },
+        })
+    };
 
-    let repo = Repo::new(&path)?;
-    let term = Term::stderr();
+    match opt.command.unwrap_or(Command::Scan(Targets::default())) {
+        Command::Scan(targets) => {
+            let scan = &opt.scan;
+            let (repos, merged) = if scan.stdin || !scan.files.is_empty() {
+                scan_text(scan.stdin, &scan.files)?
+            } else {
+                scan_all(&opt.targets.join(targets), &options)?
+            };
+            if opt.verbose {
+                for repo in &repos {
+                    eprintln!("Took {:?} to scan {}", repo.scan_time, repo.name);
+                }
+            }
+
+            let thresholds = Thresholds {
+                max_curses: scan.max_curses.or(scan.fail_on_curses.then_some(0)),
+                max_rate: scan.max_rate,
+            };
+            let all = everything(&repos);
+
+            // Submodules are nested in the JSON, but get tables of their own
+            let repos: Vec<&Repo> = if opt.json {
+                repos.iter().collect()
+            } else {
+                repos.iter().flat_map(with_submodules).collect()
+            };
+            reporter.render_all(&repos, merged.as_ref(), &mut out)?;
+            gate(&thresholds, &all)
+        }
+        Command::Words(command) => words(command, &mut out),
+        Command::Log(targets) => {
+            let (repos, _) = scan_all(&opt.targets.join(targets), &options)?;
+            reporter.render_log(&everything(&repos).records, &mut out)
+        }
+        Command::Compare {
+            directory,
+            before,
+            after,
+        } => {
+            let directory = match directory {
+                Some(directory) => directory,
+                None => env::current_dir()?,
+            };
+            let before = scan(&before, &directory, &options)?;
+            let after = scan(&after, &directory, &options)?;
+            reporter.render_comparison(&Comparison::new(&before, &after), &mut out)
+        }
+        Command::Hook {
+            install,
+            force,
+            strict,
+            severity,
+            message_file,
+        } => match message_file {
+            Some(message_file) if !install => hook(&message_file, strict, severity),
+            // `--install` is the only way to leave out the message file
+            _ => {
+                let severity = severity.to_string();
+                let mut args = vec!["--severity", &severity];
+                if strict {
+                    args.insert(0, "--strict");
+                }
+                let path = hook::install(&env::current_dir()?, &args, force)?;
+                eprintln!("Installed commit-msg hook at {}", path.display());
+                Ok(())
+            }
+        },
+        Command::Sanitize { style, targets } => {
+            let targets = opt.targets.join(targets);
+            let (repos, _) = scan_all(&targets, &sanitize::all_commits(&options))?;
+            let rewrites = sanitize::rewrites(&everything(&repos).records, style);
+            if opt.json {
+                serde_json::to_writer(&mut out, &rewrites)?;
+            } else {
+                out.write_all(sanitize::message_callback(&rewrites).as_bytes())?;
+            }
+            out.flush()?;
 
+            Ok(())
+        }
+        Command::Serve { address, targets } => {
+            let targets = opt.targets.join(targets);
+            serve::serve(address, |path| {
+                let reporter = JsonReporter {
+                    by: opt.by,
+                    summary: path == "/summary",
+                    top: display.top,
+                };
+                if !["/", "/summary", "/log"].contains(&path) {
+                    return None;
+                }
+
+                let mut body = Vec::new();
+                Some(
+                    scan_all(&targets, &options).and_then(|(repos, merged)| match path {
+                        "/log" => {
+                            reporter.render_log(&everything(&repos).records, &mut body)?;
+                            Ok(body)
+                        }
+                        _ => {
+                            let repos: Vec<&Repo> = repos.iter().collect();
+                            reporter.render_all(&repos, merged.as_ref(), &mut body)?;
+                            Ok(body)
+                        }
+                    }),
+                )
+            })
+        }
+    }
+}
+
+/// Scan all the repositories in the targets, along with all of them merged
+/// together when there are several.
+fn scan_all(
+    targets: &Targets,
+    options: &Options,
+) -> Result<(Vec<Repo>, Option<Repo>), Box<dyn Error>> {
+    let mut paths = targets.directories.clone();
+    if paths.is_empty() {
+        paths.push(env::current_dir()?);
+    }
+    if targets.recursive {
+        paths = paths
+            .iter()
+            .map(|path| Repo::find_all(path))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+    }
+
+    let term = Term::stderr();
     term.write_line("Crunching commits...")?;
+    let repos = paths
+        .iter()
+        .map(|path| Repo::with_options(path, options))
+        .collect::<Result<Vec<_>, _>>()?;
     term.clear_last_lines(1)?;
-    if verbose {
-        println!("Took {:?} to parse {}", start.elapsed(), repo.name);
+
+    let merged = match repos.as_slice() {
+        [] => return Err("no repositories found".into()),
+        [_] => None,
+        repos => Some(Repo::merged("All repositories", repos)),
+    };
+
+    Ok((repos, merged))
+}
+
+/// Fail when the repository exceeds any of the thresholds, listing the
+/// commits with curses in it.
+fn gate(thresholds: &Thresholds, repo: &Repo) -> Result<(), Box<dyn Error>> {
+    let violations = thresholds.check(repo);
+    if violations.is_empty() {
+        return Ok(());
     }
 
-    if json {
-        print_json(&repo)?;
-    } else {
-        print_table(&repo)?;
+    let mut records: Vec<_> = repo.records.iter().collect();
+    records.sort_by_key(|record| Reverse(record.time));
+    for record in records {
+        let mut curses: Vec<_> = record.curses.keys().map(String::as_str).collect();
+        curses.sort_unstable();
+        eprintln!(
+            "{} {} {}: {} ({})",
+            record.short_id(),
+            record.date(),
+            record.author.name,
+            record.summary(),
+            curses.join(", ")
+        );
+    }
+
+    Err(Box::new(ThresholdsExceeded(violations)))
+}
+
+/// Everything that was scanned merged into a single repository, for checks
+/// and listings covering all of it.
+fn everything(repos: &[Repo]) -> Repo {
+    let name = match repos {
+        [repo] => repo.name.clone(),
+        _ => "All repositories".to_owned(),
+    };
+    Repo::merged(name, repos)
+}
+
+/// The thresholds given for CI checks were exceeded.
+#[derive(Debug)]
+struct ThresholdsExceeded(Vec<String>);
+
+impl fmt::Display for ThresholdsExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.join(", "))
+    }
+}
+
+impl Error for ThresholdsExceeded {}
+
+/// Scan the text from `stdin` and files, along with all of them merged
+/// together when there are several.
+fn scan_text(stdin: bool, files: &[PathBuf]) -> Result<(Vec<Repo>, Option<Repo>), Box<dyn Error>> {
+    let mut repos = Vec::new();
+    if stdin {
+        let mut text = Vec::new();
+        io::stdin().read_to_end(&mut text)?;
+        let repo = Repo::from_text("stdin", &String::from_utf8_lossy(&text));
+        if !text.contains(&0) && repo.total_commits > 1 {
+            eprintln!(
+                "warning: no NUL characters in stdin, so each of its {} paragraphs counts as a commit, use `git log -z` to keep messages whole",
+                repo.total_commits
+            );
+        }
+        repos.push(repo);
     }
+    for path in files {
+        let text = fs::read(path).map_err(with_path(path))?;
+        repos.push(Repo::from_text(
+            path.display().to_string(),
+            &String::from_utf8_lossy(&text),
+        ));
+    }
+
+    let merged = match repos.as_slice() {
+        [_] => None,
+        repos => Some(Repo::merged("All inputs", repos)),
+    };
+
+    Ok((repos, merged))
+}
+
+/// Inspect the lists of curses.
+fn words(command: Words, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let mut tw = TabWriter::new(vec![]);
+    match command {
+        Words::Check { text } => {
+            writeln!(tw, "Word\tNormalized\tCurse\tSeverity\tList")?;
+            for token in tokenize(&text.join(" ")) {
+                match token.curse {
+                    Some(entry) => writeln!(
+                        tw,
+                        "{}\t{}\tyes\t{}\t{} ({})",
+                        token.word,
+                        token.normalized,
+                        entry.severity,
+                        entry.lexicon.name,
+                        entry.lexicon.language
+                    )?,
+                    None => writeln!(tw, "{}\t{}\tno\t-\t-", token.word, token.normalized)?,
+                }
+            }
+        }
+        Words::List { severity } => {
+            let mut entries: Vec<Entry> = LEXICONS
+                .iter()
+                .flat_map(Lexicon::entries)
+                .filter(|entry| severity.is_none_or(|severity| entry.severity >= severity))
+                .collect();
+            entries.sort_by_key(|entry| (entry.lexicon.name, entry.word));
+            writeln!(tw, "Curse\tSeverity\tLanguage\tList")?;
+            for entry in entries {
+                writeln!(
+                    tw,
+                    "{}\t{}\t{}\t{}",
+                    entry.word, entry.severity, entry.lexicon.language, entry.lexicon.name
+                )?;
+            }
+        }
+        Words::Explain { word } => explain(&word, &mut tw)?,
+    }
+    tw.flush()?;
+    out.write_all(&tw.into_inner()?)?;
+    out.flush()?;
 
     Ok(())
 }
+
+/// Explain whether a word is a curse, which list it is in and whether it can
+/// be matched at all.
+fn explain(word: &str, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let normalized = word.to_lowercase();
+    match lookup(&normalized) {
+        Some(entry) => {
+            writeln!(
+                out,
+                "`{}` is a {} curse in the `{}` list ({}) from {}",
+                entry.word,
+                entry.severity,
+                entry.lexicon.name,
+                entry.lexicon.language,
+                entry.lexicon.source
+            )?;
+            if entry.is_matchable() {
+                writeln!(
+                    out,
+                    "It matches any word that is `{}` ignoring case",
+                    entry.word
+                )?;
+            } else {
+                writeln!(
+                    out,
+                    "It never matches, since messages are split into words at anything that isn't a letter"
+                )?;
+            }
+        }
+        None => writeln!(out, "`{}` is not in any list of curses", word)?,
+    }
+
+    let tokens = tokenize(word);
+    if tokens.len() > 1 || tokens.first().is_some_and(|t| t.word != word) {
+        let words: Vec<_> = tokens.iter().map(|t| t.normalized.as_str()).collect();
+        writeln!(out, "In a message it is split into: {}", words.join(", "))?;
+        for token in tokens.iter().filter(|t| t.curse.is_some()) {
+            writeln!(out, "- `{}` is a curse on its own", token.normalized)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Warn about any curses at least as severe as `severity` in a commit
+/// message, or reject it when `strict`.
+fn hook(message_file: &Path, strict: bool, severity: Severity) -> Result<(), Box<dyn Error>> {
+    let message = fs::read_to_string(message_file).map_err(with_path(message_file))?;
+    let comment = hook::comment_prefix(&env::current_dir()?);
+    let curses: Vec<String> = hook::offending_curses(&message, &comment, severity)
+        .iter()
+        .map(|entry| format!("{} ({})", entry.word, entry.severity))
+        .collect();
+    if curses.is_empty() {
+        return Ok(());
+    }
+
+    let curses = curses.join(", ");
+    if strict {
+        eprintln!("hint: use `git commit --no-verify` to commit anyway");
+        return Err(format!("commit message contains curses: {}", curses).into());
+    }
+    eprintln!("warning: commit message contains curses: {}", curses);
+
+    Ok(())
+}
+
+/// Scan either a repository, or a revision range in the repository in
+/// `directory`.
+fn scan(target: &str, directory: &Path, options: &Options) -> Result<Repo, AngerError> {
+    if Path::new(target).is_dir() {
+        return Repo::with_options(Path::new(target), options);
+    }
+
+    let options = Options {
+        range: Some(target.to_owned()),
+        ..options.clone()
+    };
+    let mut repo = Repo::with_options(directory, &options)?;
+    repo.name = target.to_owned();
+
+    Ok(repo)
+}
+
+/// Turn an I/O error into one mentioning the file it happened to.
+fn with_path(path: &Path) -> impl FnOnce(io::Error) -> AngerError + '_ {
+    move |e| {
+        AngerError::Io(io::Error::new(
+            e.kind(),
+            format!("{}: {}", path.display(), e),
+        ))
+    }
+}
+
+/// A repository followed by all of its submodules, and their submodules.
+fn with_submodules(repo: &Repo) -> Vec<&Repo> {
+    let mut repos = vec![repo];
+    repos.extend(repo.submodules.iter().flat_map(with_submodules));
+    repos
+}
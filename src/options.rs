@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use crate::{
+    commit::Identity,
+    filter::{Pattern, is_bot},
+    team::Teams,
+};
+
+/// How curses in co-authored commits are attributed to the people involved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Attribution {
+    /// Only credit the author of the commit, ignoring any co-authors.
+    #[default]
+    Author,
+    /// Split the curses evenly between the author and all co-authors.
+    Split,
+    /// Credit the author and every co-author with all of the curses.
+    Full,
+}
+
+impl Attribution {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["author", "split", "full"];
+}
+
+impl FromStr for Attribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "author" => Ok(Attribution::Author),
+            "split" => Ok(Attribution::Split),
+            "full" => Ok(Attribution::Full),
+            _ => Err(format!("unknown attribution policy: {}", s)),
+        }
+    }
+}
+
+/// Which identity of a commit is credited with its curses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Role {
+    /// The person who originally wrote the commit.
+    #[default]
+    Author,
+    /// The person who applied the commit, e.g. after a rebase or cherry-pick.
+    Committer,
+}
+
+impl Role {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["author", "committer"];
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "author" => Ok(Role::Author),
+            "committer" => Ok(Role::Committer),
+            _ => Err(format!("unknown role: {}", s)),
+        }
+    }
+}
+
+/// Whether to display curses per author or per team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// Per author.
+    #[default]
+    Author,
+    /// Per team, which requires teams to be configured.
+    Team,
+}
+
+impl GroupBy {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["author", "team"];
+
+    /// Header of the first column in tables.
+    pub fn header(self) -> &'static str {
+        match self {
+            GroupBy::Author => "Author",
+            GroupBy::Team => "Team",
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "author" => Ok(GroupBy::Author),
+            "team" => Ok(GroupBy::Team),
+            _ => Err(format!("unknown grouping: {}", s)),
+        }
+    }
+}
+
+/// Options controlling how the commits in a repository are scanned.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// How to attribute curses in commits with `Co-authored-by` trailers.
+    pub attribution: Attribution,
+    /// Whether to credit the author or the committer of each commit.
+    pub attribute_to: Role,
+    /// Include commits by bots and service accounts, which are excluded by
+    /// default.
+    pub include_bots: bool,
+    /// Additional patterns identifying bots to exclude.
+    pub bots: Vec<Pattern>,
+    /// Only include people matching any of these patterns, if not empty.
+    pub authors: Vec<Pattern>,
+    /// Exclude people matching any of these patterns.
+    pub exclude_authors: Vec<Pattern>,
+    /// Mapping of people to teams, curses are only tallied per team when set.
+    pub teams: Option<Teams>,
+    /// Revision range to scan, like `v1.0..HEAD`, instead of all of `HEAD`.
+    pub range: Option<String>,
+    /// Scan the submodules of the repository as well.
+    pub submodules: bool,
+    /// Fold the curses in submodules into the repository instead of keeping
+    /// them separate.
+    pub fold_submodules: bool,
+}
+
+impl Options {
+    /// Checks if an identity should be excluded as a bot.
+    pub fn is_bot(&self, identity: &Identity) -> bool {
+        !self.include_bots
+            && (is_bot(identity) || self.bots.iter().any(|pattern| pattern.matches(identity)))
+    }
+
+    /// Checks if an identity is selected by the author filters.
+    pub fn is_selected(&self, identity: &Identity) -> bool {
+        (self.authors.is_empty() || self.authors.iter().any(|p| p.matches(identity)))
+            && !self.exclude_authors.iter().any(|p| p.matches(identity))
+    }
+}
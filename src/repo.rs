@@ -1,51 +1,207 @@
-use std::{collections::HashMap, env, error::Error, path::Path};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
-use git2::{Commit, Repository};
+use git2::{Commit, ErrorCode, Repository};
+#[cfg(feature = "json")]
 use serde::Serialize;
 
 use crate::{
     author::Author,
-    core::{naughty_word, split_into_clean_words},
+    commit::{CommitRecord, Identity, decode, encoding_of},
+    core::{co_authors, find_curses},
+    error::AngerError,
+    options::{Attribution, Options, Role},
+    tally::Tally,
+    team::Team,
 };
 
 /// A simple representation of a git repository.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize))]
 pub struct Repo {
     /// Name of the repository.
     pub name: String,
     /// Count of the total amount of commits in the repository.
     pub total_commits: usize,
+    /// Count of the commits containing at least one curse.
+    pub naughty_commits: usize,
     /// Count of the total amount of curses used in the commits.
     pub total_curses: usize,
     /// HashMap of all the naughty words used by the authors.
     pub curses: HashMap<String, usize>,
     /// HashMap of all the authors that have been committed.
     pub authors: HashMap<String, Author>,
+    /// HashMap of all the teams, only present when scanned with teams.
+    pub teams: HashMap<String, Team>,
+    /// Records of every commit that contained curses, newest first. They hold
+    /// full messages and emails, so they are only serialized by `log`.
+    #[cfg_attr(feature = "json", serde(skip))]
+    pub records: Vec<CommitRecord>,
+    /// HashMap of the bots that were excluded and their number of commits.
+    pub bots: HashMap<String, usize>,
+    /// Count of the commits whose message or signatures weren't valid in the
+    /// encoding they declare, which had invalid characters replaced.
+    pub lossy_commits: usize,
+    /// Time of the oldest commit in seconds since the Unix epoch.
+    pub first_commit: Option<i64>,
+    /// Time of the newest commit in seconds since the Unix epoch.
+    pub last_commit: Option<i64>,
+    /// How long it took to scan the repository.
+    #[cfg_attr(feature = "json", serde(skip))]
+    pub scan_time: Duration,
+    /// Every submodule scanned separately, unless they were folded into the
+    /// repository.
+    pub submodules: Vec<Repo>,
 }
 
 impl Repo {
-    /// Creates a new and empty repository.
-    pub fn new(path: &Path) -> Result<Self, Box<dyn Error>> {
-        let repo = Repository::open(path)?;
-        let commits = Repo::commits(&repo)?;
-
-        let repo = match path.file_name() {
-            Some(path) => path.to_str().unwrap().to_owned(),
-            None => env::current_dir()?.to_str().unwrap().to_owned(),
+    /// Creates a new repository, scanning it with the default options.
+    pub fn new(path: &Path) -> Result<Self, AngerError> {
+        Repo::with_options(path, &Options::default())
+    }
+
+    /// Creates a new repository, scanning it with the given options. The
+    /// path can be a bare repository, a linked worktree or any directory
+    /// inside a repository. A repository without any commits yet is scanned
+    /// as having zero commits.
+    pub fn with_options(path: &Path, options: &Options) -> Result<Self, AngerError> {
+        let start = Instant::now();
+        let git_repo = Repository::discover(path).map_err(|e| match e.code() {
+            ErrorCode::NotFound => AngerError::NotARepository(path.to_path_buf()),
+            _ => AngerError::Git(e),
+        })?;
+        let commits = match Repo::commits_in_range(&git_repo, options.range.as_deref()) {
+            Err(AngerError::EmptyRepository(_)) => Vec::new(),
+            commits => commits?,
+        };
+
+        let mut repo = Repo::empty(repository_name(&git_repo));
+        repo.build(commits, options);
+        if options.submodules {
+            repo.scan_submodules(&git_repo, options)?;
+        }
+        repo.scan_time = start.elapsed();
+
+        Ok(repo)
+    }
+
+    /// Scan every submodule that has been checked out, either keeping them
+    /// separate or folding them into this repository.
+    fn scan_submodules(&mut self, repo: &Repository, options: &Options) -> Result<(), AngerError> {
+        let options = Options {
+            range: None,
+            ..options.clone()
         };
+        for submodule in repo.submodules()? {
+            let Ok(sub_repo) = submodule.open() else {
+                continue;
+            };
+            let path = sub_repo.workdir().unwrap_or_else(|| sub_repo.path());
+            let mut sub = Repo::with_options(path, &options)?;
+            sub.name = submodule.name().unwrap_or(&sub.name).to_owned();
+
+            if options.fold_submodules {
+                self.merge(&sub);
+            } else {
+                self.submodules.push(sub);
+            }
+        }
 
-        let mut repo = Repo {
-            name: repo,
+        Ok(())
+    }
+
+    /// Creates an empty repository without any commits.
+    pub fn empty(name: impl Into<String>) -> Self {
+        Repo {
+            name: name.into(),
             total_commits: 0,
+            naughty_commits: 0,
             total_curses: 0,
             curses: HashMap::new(),
             authors: HashMap::new(),
-        };
+            teams: HashMap::new(),
+            records: Vec::new(),
+            bots: HashMap::new(),
+            lossy_commits: 0,
+            first_commit: None,
+            last_commit: None,
+            scan_time: Duration::ZERO,
+            submodules: Vec::new(),
+        }
+    }
 
-        repo.build(commits);
-        repo.count_curses();
+    /// Merges several repositories into one, where authors and teams with the
+    /// same name are unified.
+    pub fn merged<'a>(name: impl Into<String>, repos: impl IntoIterator<Item = &'a Repo>) -> Self {
+        let mut merged = Repo::empty(name);
+        for repo in repos {
+            merged.merge(repo);
+        }
+        merged.records.sort_by_key(|r| Reverse(r.time));
 
-        Ok(repo)
+        merged
+    }
+
+    /// Adds all the commits, curses, authors and teams of another repository,
+    /// along with those of its submodules.
+    pub fn merge(&mut self, other: &Repo) {
+        self.total_commits += other.total_commits;
+        self.naughty_commits += other.naughty_commits;
+        self.total_curses += other.total_curses;
+        merge_counts(&mut self.curses, &other.curses);
+        merge_counts(&mut self.bots, &other.bots);
+        self.lossy_commits += other.lossy_commits;
+        self.first_commit = self
+            .first_commit
+            .into_iter()
+            .chain(other.first_commit)
+            .min();
+        self.last_commit = self.last_commit.into_iter().chain(other.last_commit).max();
+        self.scan_time += other.scan_time;
+
+        for author in other.authors.values() {
+            self.author(&author.name).merge(author);
+        }
+        for team in other.teams.values() {
+            self.team(&team.name).merge(team);
+        }
+        self.records.extend(other.records.iter().cloned());
+        for submodule in &other.submodules {
+            self.merge(submodule);
+        }
+    }
+
+    /// Finds all git repositories in a directory and its subdirectories,
+    /// without looking inside the repositories that are found.
+    pub fn find_all(root: &Path) -> Result<Vec<PathBuf>, AngerError> {
+        let mut repos = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            if dir.join(".git").exists() || is_bare(&dir) {
+                repos.push(dir);
+                continue;
+            }
+
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if dir == root => return Err(e.into()),
+                Err(_) => continue,
+            };
+            for entry in entries.flatten() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !hidden && entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    dirs.push(entry.path());
+                }
+            }
+        }
+        repos.sort();
+
+        Ok(repos)
     }
 
     /// Checks if an author exists and creates a new author if she/he doesn't
@@ -60,16 +216,11 @@ impl Repo {
         self.authors.get_mut(author_name).expect("exists")
     }
 
-    /// Counts all the naughty words used by authors.
-    pub fn count_curses(&mut self) {
-        for author in self.authors.values() {
-            for (name, curse) in &author.curses {
-                self.curses
-                    .entry(name.to_string())
-                    .and_modify(|c| *c += *curse)
-                    .or_insert_with(|| *curse);
-            }
-        }
+    /// Checks if a team exists and creates a new team if it doesn't exist.
+    pub fn team(&mut self, team_name: &str) -> &mut Team {
+        self.teams
+            .entry(team_name.into())
+            .or_insert_with(|| Team::new(team_name))
     }
 
     /// Count total naughty authors in repository.
@@ -77,11 +228,46 @@ impl Repo {
         self.authors.values().filter(|a| a.is_naughty()).count()
     }
 
+    /// Count the commits by bots that were excluded from the totals.
+    pub fn excluded_commits(&self) -> usize {
+        self.bots.values().sum()
+    }
+
     /// Build a list of commits by walking the history of a repository.
-    pub fn commits(repo: &Repository) -> Result<Vec<Commit<'_>>, Box<dyn Error>> {
+    pub fn commits(repo: &Repository) -> Result<Vec<Commit<'_>>, AngerError> {
+        Repo::commits_in_range(repo, None)
+    }
+
+    /// Build a list of commits by walking a revision range like `v1.0..HEAD`,
+    /// a single revision like `main` or `HEAD` when no range is given.
+    pub fn commits_in_range<'r>(
+        repo: &'r Repository,
+        range: Option<&str>,
+    ) -> Result<Vec<Commit<'r>>, AngerError> {
         let mut revwalk = repo.revwalk()?;
         let mut commits: Vec<Commit> = Vec::new();
-        revwalk.push_head()?;
+        let pushed = match range {
+            Some(range) if range.contains("..") => revwalk.push_range(range),
+            Some(revision) => repo
+                .revparse_single(revision)
+                .and_then(|object| object.peel_to_commit())
+                .and_then(|commit| revwalk.push(commit.id())),
+            None => revwalk.push_head(),
+        };
+        pushed.map_err(|e| match (e.code(), range) {
+            // Empty repositories and orphan branches have no commits to walk
+            (_, None)
+                if repo
+                    .head()
+                    .is_err_and(|e| e.code() == ErrorCode::UnbornBranch) =>
+            {
+                AngerError::EmptyRepository(repo.workdir().unwrap_or(repo.path()).to_path_buf())
+            }
+            (ErrorCode::NotFound | ErrorCode::InvalidSpec | ErrorCode::Ambiguous, Some(range)) => {
+                AngerError::InvalidRevision(range.to_owned())
+            }
+            _ => AngerError::Git(e),
+        })?;
         for commit_id in revwalk {
             let commit = repo.find_commit(commit_id?)?;
             commits.push(commit);
@@ -91,33 +277,260 @@ impl Repo {
     }
 
     /// Iterate over all commits, finding authors who have been naughty and
-    /// keep track of them.
-    pub fn build(&mut self, commits: Vec<Commit>) {
+    /// keep track of them. Curses are credited to either the author or the
+    /// committer of a commit, and to its co-authors according to the
+    /// attribution policy in `options`. Commits by bots are counted separately
+    /// and people not selected by the author filters are skipped.
+    pub fn build(&mut self, commits: Vec<Commit>, options: &Options) {
         for commit in &commits {
-            if let (Some(author_name), Some(commit_message)) = (
-                commit.author().name(),
-                commit.message().map(|w| w.to_lowercase()),
-            ) {
-                let mut curses_added = 0;
-                {
-                    let author = self.author(author_name);
-                    author.total_commits += 1;
-                    for word in split_into_clean_words(&commit_message) {
-                        if naughty_word(word) {
-                            author.total_curses += 1;
-                            curses_added += 1;
-                            author.update_occurrence(word);
-                        }
+            let encoding = encoding_of(commit);
+            let (author, bad_author) = Identity::from_signature(&commit.author(), encoding);
+            let (committer, bad_committer) =
+                Identity::from_signature(&commit.committer(), encoding);
+            let (message, bad_message) = decode(commit.message_bytes(), encoding);
+            if bad_author || bad_committer || bad_message {
+                self.lossy_commits += 1;
+            }
+
+            let credited = match options.attribute_to {
+                Role::Author => &author,
+                Role::Committer => &committer,
+            };
+            if options.is_bot(credited) {
+                *self.bots.entry(credited.name.clone()).or_insert(0) += 1;
+                continue;
+            }
+
+            let mut people = vec![credited.clone()];
+            if options.attribution != Attribution::Author {
+                for (name, email) in co_authors(&message) {
+                    let co_author = Identity {
+                        name: name.into(),
+                        email: email.into(),
+                    };
+                    if people.iter().all(|p| p.name != name) && !options.is_bot(&co_author) {
+                        people.push(co_author);
                     }
                 }
-                self.total_commits += 1;
-                self.total_curses += curses_added;
-            } else {
-                eprintln!(
-                    "Skipping commit {:?} because either the commit author or message is missing",
-                    commit
-                );
             }
+            // Curses are split between everyone, even those filtered out
+            let weight = match options.attribution {
+                Attribution::Split => 1.0 / people.len() as f64,
+                Attribution::Author | Attribution::Full => 1.0,
+            };
+            people.retain(|person| options.is_selected(person));
+            if people.is_empty() {
+                continue;
+            }
+
+            let curses = find_curses(&message);
+            let commit_curses = self.tally(&people, &curses, weight, options);
+
+            let time = commit.time().seconds();
+            self.first_commit = Some(self.first_commit.map_or(time, |first| first.min(time)));
+            self.last_commit = Some(self.last_commit.map_or(time, |last| last.max(time)));
+
+            if !curses.is_empty() {
+                self.records.push(CommitRecord {
+                    id: commit.id().to_string(),
+                    author,
+                    committer,
+                    credited: people.into_iter().map(|person| person.name).collect(),
+                    time,
+                    message,
+                    curses: commit_curses,
+                });
+            }
+        }
+    }
+
+    /// Scans text instead of a repository, like the commit messages from
+    /// `git log -z --format=%B` or a chat export. Messages are separated by
+    /// NUL characters when there are any, otherwise every paragraph counts as
+    /// a message. They are all credited to someone named after the text.
+    pub fn from_text(name: impl Into<String>, text: &str) -> Self {
+        let start = Instant::now();
+        let mut repo = Repo::empty(name);
+        let people = [Identity {
+            name: repo.name.clone(),
+            email: String::new(),
+        }];
+        let messages = if text.contains('\0') {
+            text.split('\0')
+                .filter(|message| !message.trim().is_empty())
+                .map(str::to_owned)
+                .collect()
+        } else {
+            paragraphs(text)
+        };
+        for message in messages {
+            repo.tally(&people, &find_curses(&message), 1.0, &Options::default());
+        }
+        repo.scan_time = start.elapsed();
+
+        repo
+    }
+
+    /// Counts the curses in a message for everyone credited with it, weighted
+    /// by `weight`, their teams and the repository, returning how often each
+    /// curse was used.
+    fn tally(
+        &mut self,
+        people: &[Identity],
+        curses: &[String],
+        weight: f64,
+        options: &Options,
+    ) -> HashMap<String, usize> {
+        let mut teams: HashMap<&str, f64> = HashMap::new();
+        for person in people {
+            let author = self.author(&person.name);
+            author.total_commits += 1;
+            author.naughty_commits += usize::from(!curses.is_empty());
+            author.total_curses += curses.len();
+            author.score += curses.len() as f64 * weight;
+            for curse in curses {
+                author.update_occurrence(curse);
+            }
+
+            if let Some(config) = &options.teams {
+                let team = config.team_of(person);
+                *teams.entry(team).or_insert(0.0) += weight;
+                self.team(team).members.insert(person.name.clone());
+            }
+        }
+
+        for (name, weight) in teams {
+            let team = self.team(name);
+            team.total_commits += 1;
+            team.naughty_commits += usize::from(!curses.is_empty());
+            team.total_curses += curses.len();
+            team.score += curses.len() as f64 * weight;
+            for curse in curses {
+                team.update_occurrence(curse);
+            }
+        }
+
+        self.total_commits += 1;
+        self.total_curses += curses.len();
+        self.naughty_commits += usize::from(!curses.is_empty());
+        let mut counts = HashMap::new();
+        for curse in curses {
+            *self.curses.entry(curse.clone()).or_insert(0) += 1;
+            *counts.entry(curse.clone()).or_insert(0) += 1;
+        }
+
+        counts
+    }
+}
+
+/// Splits text into paragraphs separated by blank lines.
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut lines = Vec::new();
+    for line in text.lines().chain([""]) {
+        if !line.trim().is_empty() {
+            lines.push(line);
+        } else if !lines.is_empty() {
+            paragraphs.push(lines.join("\n"));
+            lines.clear();
+        }
+    }
+
+    paragraphs
+}
+
+impl Tally for Repo {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn total_commits(&self) -> usize {
+        self.total_commits
+    }
+
+    fn naughty_commits(&self) -> usize {
+        self.naughty_commits
+    }
+
+    fn total_curses(&self) -> usize {
+        self.total_curses
+    }
+
+    fn score(&self) -> f64 {
+        self.total_curses as f64
+    }
+
+    fn curses(&self) -> &HashMap<String, usize> {
+        &self.curses
+    }
+}
+
+/// Checks if a directory looks like a bare repository.
+fn is_bare(dir: &Path) -> bool {
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
+/// Derive the name of a repository from the URL of its `origin` remote, or
+/// any other remote, falling back to the name of its working directory or the
+/// bare repository itself.
+fn repository_name(repo: &Repository) -> String {
+    let remote = repo.find_remote("origin").ok().or_else(|| {
+        let remotes = repo.remotes().ok()?;
+        let name = remotes.iter().flatten().next()?;
+        repo.find_remote(name).ok()
+    });
+    if let Some(name) = remote
+        .as_ref()
+        .and_then(|r| r.url())
+        .and_then(name_from_url)
+    {
+        return name;
+    }
+
+    let dir = repo.workdir().unwrap_or_else(|| repo.path());
+    match dir.file_name() {
+        Some(name) => {
+            let name = name.to_string_lossy();
+            name.strip_suffix(".git").unwrap_or(&name).to_owned()
+        }
+        None => dir.to_string_lossy().into_owned(),
+    }
+}
+
+/// Find the name of a repository in a URL like
+/// `git@github.com:sondr3/git-anger-management.git`.
+fn name_from_url(url: &str) -> Option<String> {
+    let name = url.trim_end_matches('/').rsplit(['/', ':', '\\']).next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Add all the counts in `other` to `counts`.
+pub(crate) fn merge_counts(counts: &mut HashMap<String, usize>, other: &HashMap<String, usize>) {
+    for (name, count) in other {
+        *counts.entry(name.clone()).or_insert(0) += count;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_name_from_url() {
+        for url in [
+            "git@github.com:sondr3/git-anger-management.git",
+            "https://github.com/sondr3/git-anger-management/",
+            "/srv/git/git-anger-management.git",
+            "git-anger-management",
+        ] {
+            assert_eq!(name_from_url(url).unwrap(), "git-anger-management");
         }
+        assert_eq!(
+            name_from_url("https://example.com/"),
+            Some("example.com".into())
+        );
+        assert_eq!(name_from_url(""), None);
     }
 }
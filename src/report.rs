@@ -0,0 +1,49 @@
+use std::{error::Error, io::Write};
+
+use crate::{commit::CommitRecord, compare::Comparison, repo::Repo};
+
+/// Renders repositories in some output format, like JSON or a table.
+pub trait Reporter {
+    /// Render a single repository into `out`.
+    fn render(&self, repo: &Repo, out: &mut dyn Write) -> Result<(), Box<dyn Error>>;
+
+    /// Render how the curses changed between two repositories or revision
+    /// ranges into `out`.
+    fn render_comparison(
+        &self,
+        comparison: &Comparison,
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Render commits containing curses into `out`.
+    fn render_log(
+        &self,
+        records: &[CommitRecord],
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Render several repositories along with all of them merged together,
+    /// by default one after the other with their names as titles. A single
+    /// repository is rendered on its own.
+    fn render_all(
+        &self,
+        repos: &[&Repo],
+        merged: Option<&Repo>,
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut all = repos.to_vec();
+        all.extend(merged);
+        match all.as_slice() {
+            [repo] => self.render(repo, out)?,
+            all => {
+                for repo in all {
+                    writeln!(out, "{}\n", repo.name)?;
+                    self.render(repo, out)?;
+                    writeln!(out)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
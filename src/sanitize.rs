@@ -0,0 +1,209 @@
+use std::str::FromStr;
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{
+    commit::CommitRecord,
+    core::is_co_author_trailer,
+    lexicon::{Entry, lookup},
+    options::Options,
+};
+
+/// How curses are replaced when sanitizing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// Keep the first letter and mask the rest, like `f***`.
+    #[default]
+    Mask,
+    /// Use a milder synonym from the list of curses, masking curses without
+    /// one.
+    Synonym,
+}
+
+impl Style {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["mask", "synonym"];
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mask" => Ok(Style::Mask),
+            "synonym" => Ok(Style::Synonym),
+            _ => Err(format!("unknown style: {}", s)),
+        }
+    }
+}
+
+/// A commit message along with its sanitized version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Rewrite {
+    /// The commit hash.
+    pub commit: String,
+    /// The message as it was committed.
+    pub original: String,
+    /// The message without any curses.
+    pub sanitized: String,
+}
+
+/// Replaces every curse in a message, keeping everything else as it is. Any
+/// `Co-authored-by` trailers are kept as well, since names aren't curses.
+pub fn sanitize(message: &str, style: Style) -> String {
+    let mut sanitized = String::with_capacity(message.len());
+    for line in message.split_inclusive('\n') {
+        if is_co_author_trailer(line) {
+            sanitized.push_str(line);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find(char::is_alphabetic) {
+            sanitized.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            match lookup(word) {
+                Some(entry) => sanitized.push_str(&replace(word, entry, style)),
+                None => sanitized.push_str(word),
+            }
+            rest = &rest[end..];
+        }
+        sanitized.push_str(rest);
+    }
+
+    sanitized
+}
+
+/// Options for scanning every commit, since cleaning up history mustn't miss
+/// commits by bots or by authors that are filtered out.
+pub fn all_commits(options: &Options) -> Options {
+    Options {
+        include_bots: true,
+        bots: Vec::new(),
+        authors: Vec::new(),
+        exclude_authors: Vec::new(),
+        ..options.clone()
+    }
+}
+
+/// Sanitizes the message of every commit containing curses.
+pub fn rewrites(records: &[CommitRecord], style: Style) -> Vec<Rewrite> {
+    records
+        .iter()
+        .map(|record| Rewrite {
+            commit: record.id.clone(),
+            original: record.message.clone(),
+            sanitized: sanitize(&record.message, style),
+        })
+        .filter(|rewrite| rewrite.original != rewrite.sanitized)
+        .collect()
+}
+
+/// A callback for `git filter-repo --message-callback` replacing the original
+/// messages with the sanitized ones. Messages that weren't UTF-8 to begin
+/// with are left alone, since they never match.
+pub fn message_callback(rewrites: &[Rewrite]) -> String {
+    let mut callback = String::from(
+        "# Generated by git-anger-management, use it with\n\
+         # git filter-repo --message-callback \"$(cat <this file>)\"\n\
+         rewrites = {\n",
+    );
+    let mut seen = Vec::new();
+    for rewrite in rewrites {
+        if !seen.contains(&&rewrite.original) {
+            seen.push(&rewrite.original);
+            callback.push_str(&format!(
+                "    {}: {},\n",
+                python_bytes(&rewrite.original),
+                python_bytes(&rewrite.sanitized)
+            ));
+        }
+    }
+    callback.push_str("}\nreturn rewrites.get(message, message)\n");
+
+    callback
+}
+
+/// Replaces a curse according to the style, keeping its case.
+fn replace(word: &str, entry: Entry, style: Style) -> String {
+    match (style, entry.synonym()) {
+        (Style::Synonym, Some(synonym))
+            if word.chars().count() > 1 && !word.chars().any(char::is_lowercase) =>
+        {
+            synonym.to_uppercase()
+        }
+        (Style::Synonym, Some(synonym)) if word.starts_with(char::is_uppercase) => {
+            let mut chars = synonym.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        (Style::Synonym, Some(synonym)) => synonym.to_owned(),
+        _ => {
+            let mut chars = word.chars();
+            chars.next().into_iter().chain(chars.map(|_| '*')).collect()
+        }
+    }
+}
+
+/// A Python bytes literal of the UTF-8 in a string.
+fn python_bytes(text: &str) -> String {
+    let mut literal = String::from("b\"");
+    for byte in text.bytes() {
+        match byte {
+            b'\\' => literal.push_str("\\\\"),
+            b'"' => literal.push_str("\\\""),
+            b'\n' => literal.push_str("\\n"),
+            b' '..=b'~' => literal.push(char::from(byte)),
+            _ => literal.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    literal.push('"');
+
+    literal
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let message = "Fix DAMN bug, shit-for-brains\n\nFucking hell\nCo-authored-by: Dick <dick@example.com>\n";
+        assert_eq!(
+            sanitize(message, Style::Mask),
+            "Fix D*** bug, s***-for-brains\n\nF****** h***\nCo-authored-by: Dick <dick@example.com>\n"
+        );
+        assert_eq!(
+            sanitize(message, Style::Synonym),
+            "Fix DARN bug, stuff-for-brains\n\nFreaking heck\nCo-authored-by: Dick <dick@example.com>\n"
+        );
+        assert_eq!(sanitize("Add tests", Style::Mask), "Add tests");
+    }
+
+    #[test]
+    fn test_message_callback() {
+        let rewrite = Rewrite {
+            commit: "abc".into(),
+            original: "Fix \"damn\" bug ø\n".into(),
+            sanitized: "Fix \"d***\" bug ø\n".into(),
+        };
+        let callback = message_callback(&[rewrite.clone(), rewrite]);
+        assert_eq!(
+            callback.lines().skip(2).collect::<Vec<_>>(),
+            [
+                "rewrites = {",
+                r#"    b"Fix \"damn\" bug \xc3\xb8\n": b"Fix \"d***\" bug \xc3\xb8\n","#,
+                "}",
+                "return rewrites.get(message, message)"
+            ]
+        );
+    }
+}
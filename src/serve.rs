@@ -0,0 +1,141 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    time::Duration,
+};
+
+/// How long to wait on a client before giving up on it, since a single slow
+/// client holds up everyone else.
+const TIMEOUT: Duration = Duration::from_secs(2);
+/// The longest request line or header accepted, in bytes.
+const MAX_LINE: u64 = 8 * 1024;
+/// The most headers accepted in a request.
+const MAX_HEADERS: usize = 100;
+
+/// The body of a response, or `None` when nothing exists at the path.
+pub type Response = Option<Result<Vec<u8>, Box<dyn Error>>>;
+
+/// Serve JSON over HTTP, answering every `GET` request with whatever
+/// `respond` renders for its path. Requests are handled one at a time.
+pub fn serve(
+    address: SocketAddr,
+    respond: impl Fn(&str) -> Response,
+) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(address)?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        if let Err(e) = stream.map_err(Box::from).and_then(|s| handle(s, &respond)) {
+            eprintln!("error: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a single request and write the response.
+fn handle(
+    mut stream: TcpStream,
+    respond: &impl Fn(&str) -> Response,
+) -> Result<(), Box<dyn Error>> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let request = read_request(&mut BufReader::new(&stream))?;
+
+    let mut parts = request.as_deref().unwrap_or_default().split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        _ if request.is_none() => ("400 Bad Request", error("request too large")),
+        (Some("GET"), Some(target)) => {
+            let path = target.split('?').next().unwrap_or(target);
+            match respond(path) {
+                Some(Ok(body)) => ("200 OK", body),
+                Some(Err(e)) => ("500 Internal Server Error", error(&e.to_string())),
+                None => ("404 Not Found", error("not found")),
+            }
+        }
+        _ => (
+            "405 Method Not Allowed",
+            error("only GET requests are supported"),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        body.len()
+    )?;
+    stream.write_all(&body)?;
+    stream.flush()?;
+    if request.is_none() {
+        // Closing with unread data resets the connection before the client
+        // gets the response, so read a bit more of what it sent first
+        stream.shutdown(Shutdown::Write)?;
+        let limit = MAX_LINE * MAX_HEADERS as u64;
+        io::copy(&mut (&stream).take(limit), &mut io::sink())?;
+    }
+
+    Ok(())
+}
+
+/// Read the request line, skipping the headers since requests without a body
+/// are all we care about. Returns `None` for requests that are too large.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let Some(request) = read_line(reader)? else {
+        return Ok(None);
+    };
+    for _ in 0..MAX_HEADERS {
+        match read_line(reader)? {
+            Some(header) if header.trim().is_empty() => return Ok(Some(request)),
+            Some(_) => {}
+            None => return Ok(None),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Read a single line, or `None` if it is longer than `MAX_LINE`.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE).read_line(&mut line)?;
+    let complete = line.ends_with('\n') || (line.len() as u64) < MAX_LINE;
+
+    Ok(complete.then_some(line))
+}
+
+/// A JSON-object describing an error.
+fn error(message: &str) -> Vec<u8> {
+    serde_json::json!({ "error": message })
+        .to_string()
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn read(request: &str) -> Option<String> {
+        read_request(&mut request.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = "GET /summary HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n";
+        assert_eq!(read(request).as_deref(), Some("GET /summary HTTP/1.1\r\n"));
+
+        let long = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE as usize));
+        assert_eq!(read(&long), None);
+
+        let long = format!(
+            "GET / HTTP/1.1\r\nCookie: {}\r\n\r\n",
+            "a".repeat(MAX_LINE as usize)
+        );
+        assert_eq!(read(&long), None);
+
+        let headers = "X-Header: 1\r\n".repeat(MAX_HEADERS);
+        assert_eq!(read(&format!("GET / HTTP/1.1\r\n{}\r\n", headers)), None);
+        let headers = "X-Header: 1\r\n".repeat(MAX_HEADERS - 1);
+        assert!(read(&format!("GET / HTTP/1.1\r\n{}\r\n", headers)).is_some());
+    }
+}
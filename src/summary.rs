@@ -0,0 +1,71 @@
+use std::{cmp::Reverse, time::Duration};
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{repo::Repo, tally::Tally};
+
+/// How many of the most used curses a summary lists by default.
+pub const TOP_CURSES: usize = 5;
+
+/// A summary of a whole repository, without the curses of every author.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Summary {
+    /// Name of the repository.
+    pub name: String,
+    /// Count of the total amount of commits.
+    pub total_commits: usize,
+    /// Count of the commits containing at least one curse.
+    pub naughty_commits: usize,
+    /// Count of the authors.
+    pub total_authors: usize,
+    /// Count of the authors who have cursed at least once.
+    pub naughty_authors: usize,
+    /// Count of the total amount of curses.
+    pub total_curses: usize,
+    /// Average number of curses per commit.
+    pub curses_per_commit: f64,
+    /// The most used curses and their counts, the most used first.
+    pub top_curses: Vec<(String, usize)>,
+    /// Time of the oldest commit in seconds since the Unix epoch.
+    pub first_commit: Option<i64>,
+    /// Time of the newest commit in seconds since the Unix epoch.
+    pub last_commit: Option<i64>,
+    /// How long it took to scan the repository, in seconds in JSON.
+    #[cfg_attr(feature = "json", serde(serialize_with = "as_seconds"))]
+    pub scan_time: Duration,
+}
+
+impl Summary {
+    /// Summarize a repository, listing the `top` most used curses.
+    pub fn new(repo: &Repo, top: usize) -> Self {
+        let mut top_curses: Vec<(String, usize)> = repo
+            .curses
+            .iter()
+            .map(|(curse, count)| (curse.clone(), *count))
+            .collect();
+        top_curses.sort_by(|(a, i), (b, j)| (Reverse(i), a).cmp(&(Reverse(j), b)));
+        top_curses.truncate(top);
+
+        Summary {
+            name: repo.name.clone(),
+            total_commits: repo.total_commits,
+            naughty_commits: repo.naughty_commits,
+            total_authors: repo.authors.len(),
+            naughty_authors: repo.total_naughty_authors(),
+            total_curses: repo.total_curses,
+            curses_per_commit: repo.curses_per_commit(),
+            top_curses,
+            first_commit: repo.first_commit,
+            last_commit: repo.last_commit,
+            scan_time: repo.scan_time,
+        }
+    }
+}
+
+/// Serialize a duration as fractional seconds.
+#[cfg(feature = "json")]
+fn as_seconds<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
@@ -0,0 +1,823 @@
+use std::{cmp::Reverse, collections::HashMap, error::Error, io, io::Write, iter, str::FromStr};
+
+use console::Style;
+use tabwriter::TabWriter;
+
+use crate::{
+    commit::{CommitRecord, format_date},
+    compare::{Comparison, Delta},
+    core::naughty_word,
+    options::GroupBy,
+    repo::Repo,
+    report::Reporter,
+    summary::{Summary, TOP_CURSES},
+    tally::Tally,
+    team::Team,
+};
+
+/// Columns summarizing each row: total curses, curses per commit and the
+/// percentage of naughty commits.
+const SUMMARY_COLUMNS: &[&str] = &["Total", "Per commit", "% naughty"];
+
+/// Header of the column with each row's share of the curses in co-authored
+/// commits, only shown when curses are split between co-authors.
+const SHARE: &str = "Share";
+
+/// Header of the column that curses, authors or teams beyond
+/// `max_columns` are folded into.
+const OTHER: &str = "other";
+
+/// How to order the rows and curse columns of the table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SortBy {
+    /// Alphabetically by name.
+    Name,
+    /// By total curses, highest first.
+    Total,
+    /// By curses per commit, highest first.
+    Rate,
+    /// By percentage of commits containing curses, highest first.
+    Percent,
+    /// By share of the curses, highest first.
+    Score,
+    /// By how often a specific curse was used, highest first.
+    Word(String),
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(SortBy::Name),
+            "total" => Ok(SortBy::Total),
+            "rate" => Ok(SortBy::Rate),
+            "percent" => Ok(SortBy::Percent),
+            "score" => Ok(SortBy::Score),
+            word if naughty_word(&word.to_lowercase()) => Ok(SortBy::Word(word.to_lowercase())),
+            _ => Err(format!("unknown sort order or curse: {}", s)),
+        }
+    }
+}
+
+/// How to lay out the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Use the wide layout if it fits in the terminal, otherwise transpose it.
+    Auto,
+    /// One row per author or team and one column per curse.
+    Wide,
+    /// One row per curse and one column per author or team.
+    Transposed,
+}
+
+impl Layout {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["auto", "wide", "transposed"];
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Layout::Auto),
+            "wide" => Ok(Layout::Wide),
+            "transposed" => Ok(Layout::Transposed),
+            _ => Err(format!("unknown layout: {}", s)),
+        }
+    }
+}
+
+/// When to color the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Only when printing to a terminal and `NO_COLOR` isn't set.
+    Auto,
+    /// Always color the output.
+    Always,
+    /// Never color the output.
+    Never,
+}
+
+impl ColorChoice {
+    /// All the possible values, used for parsing command line arguments.
+    pub const VARIANTS: &'static [&'static str] = &["auto", "always", "never"];
+
+    /// Checks if the output on `stdout` should be colored.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => console::colors_enabled(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("unknown color choice: {}", s)),
+        }
+    }
+}
+
+/// Options for how to lay out the table.
+#[derive(Debug, Clone)]
+pub struct TableOptions {
+    /// Whether to display curses per author or per team.
+    pub by: GroupBy,
+    /// How to order the rows and curse columns.
+    pub sort_by: SortBy,
+    /// Reverse the order of the rows and curse columns.
+    pub reverse: bool,
+    /// Only show this many rows and curse columns.
+    pub top: Option<usize>,
+    /// Whether to show curses as columns or rows.
+    pub layout: Layout,
+    /// Fold any columns beyond this many into a single column.
+    pub max_columns: Option<usize>,
+    /// Color the table like a heatmap.
+    pub colored: bool,
+    /// Width the automatic layout fits the table into, usually the width of
+    /// the terminal. The table is always wide when there is no width.
+    pub width: Option<usize>,
+    /// Show a small table per author with only their own curses instead of a
+    /// single table of everyone.
+    pub author_detail: bool,
+    /// Only summarize the repository as a whole.
+    pub summary: bool,
+}
+
+/// Renders repositories as tables.
+#[derive(Debug, Clone)]
+pub struct TableReporter {
+    /// How to lay out the tables.
+    pub options: TableOptions,
+}
+
+impl Reporter for TableReporter {
+    fn render(&self, repo: &Repo, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+        if self.options.summary {
+            write!(out, "{}", summary(repo, &self.options)?)?;
+        } else if self.options.author_detail {
+            write!(out, "{}", author_details(repo, &self.options)?)?;
+        } else {
+            let (table, show_total) = build_table(repo, &self.options);
+            write!(out, "{}", table.render(show_total, self.options.colored)?)?;
+        }
+        table_notes(repo, out)?;
+        out.flush()?;
+
+        Ok(())
+    }
+
+    fn render_comparison(
+        &self,
+        comparison: &Comparison,
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let tables = comparison_tables(comparison)
+            .iter()
+            .map(|table| table.render(false, false))
+            .collect::<Result<Vec<_>, _>>()?;
+        write!(out, "{}", tables.join("\n"))?;
+        out.flush()?;
+
+        Ok(())
+    }
+
+    fn render_log(
+        &self,
+        records: &[CommitRecord],
+        out: &mut dyn Write,
+    ) -> Result<(), Box<dyn Error>> {
+        let header = ["Commit", "Date", "Author", "Curses", "Summary"];
+        let rows = records
+            .iter()
+            .map(|record| {
+                let mut curses: Vec<&str> = record.curses.keys().map(String::as_str).collect();
+                curses.sort_unstable();
+                vec![
+                    record.short_id().to_owned().into(),
+                    record.date().into(),
+                    record.author.name.clone().into(),
+                    curses.join(", ").into(),
+                    record.summary().to_owned().into(),
+                ]
+            })
+            .collect();
+        let table = Table {
+            header: header.iter().map(|h| Cell::from(h.to_string())).collect(),
+            rows,
+            footer: Vec::new(),
+        };
+        write!(out, "{}", table.render(false, false)?)?;
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Build a table to display naughty authors or teams and their words, and
+/// print it.
+pub fn print_table(repo: &Repo, options: &TableOptions) -> Result<(), Box<dyn Error>> {
+    TableReporter {
+        options: options.clone(),
+    }
+    .render(repo, &mut io::stdout())
+}
+
+/// Build a table of the naughty authors or teams and their words, along with
+/// whether to show the total of all the rows.
+fn build_table(repo: &Repo, options: &TableOptions) -> (Table, bool) {
+    let curses = sort(&repo.curses, options);
+    let rows: Vec<&dyn Tally> = match options.by {
+        GroupBy::Author => repo.authors.values().map(|a| a as &dyn Tally).collect(),
+        GroupBy::Team => repo.teams.values().map(|t| t as &dyn Tally).collect(),
+    };
+    let rows: Vec<_> = rows.into_iter().filter(|r| r.total_curses() > 0).collect();
+    let show_total = rows.len() > 1;
+    let rows = sort_rows(rows, options);
+
+    let build = |layout: Layout, max_columns: Option<usize>| match layout {
+        Layout::Transposed => transposed_table(repo, options.by, &rows, &curses, max_columns),
+        Layout::Wide | Layout::Auto => wide_table(repo, options.by, &rows, &curses, max_columns),
+    };
+
+    let table = match (options.layout, options.width) {
+        (Layout::Auto, Some(width)) => {
+            let fits = |table: &Table| table.width() <= width;
+            let wide = build(Layout::Wide, options.max_columns);
+            if fits(&wide) {
+                wide
+            } else {
+                // Tables only get wider with more columns, so bisect for the
+                // most columns that fit, falling back to a single one
+                let (mut fitting, mut too_many) =
+                    (1, options.max_columns.unwrap_or(rows.len()).max(1) + 1);
+                while too_many - fitting > 1 {
+                    let columns = (fitting + too_many) / 2;
+                    if fits(&build(Layout::Transposed, Some(columns))) {
+                        fitting = columns;
+                    } else {
+                        too_many = columns;
+                    }
+                }
+                build(Layout::Transposed, Some(fitting))
+            }
+        }
+        (layout, _) => build(layout, options.max_columns),
+    };
+
+    (table, show_total)
+}
+
+/// Create a sorted `Vec` from a HashMap of curses, sorted alphabetically when
+/// sorting by name and by counts otherwise.
+fn sort(curses: &HashMap<String, usize>, options: &TableOptions) -> Vec<(String, usize)> {
+    let mut curses: Vec<(&String, &usize)> = curses.iter().collect();
+    curses.sort_by_key(|(a, _)| *a);
+    match &options.sort_by {
+        SortBy::Name => {}
+        SortBy::Word(word) => curses.sort_by_key(|(c, i)| (*c != word, Reverse(**i))),
+        _ => curses.sort_by_key(|(_, i)| Reverse(**i)),
+    }
+    if options.reverse {
+        curses.reverse();
+    }
+    if let Some(top) = options.top {
+        curses.truncate(top);
+    }
+
+    curses
+        .iter()
+        .map(|(c, i)| ((*c).to_string(), **i))
+        .collect()
+}
+
+/// Sort the rows of the table, the naughtiest first unless sorting by name.
+fn sort_rows<'a>(mut rows: Vec<&'a dyn Tally>, options: &TableOptions) -> Vec<&'a dyn Tally> {
+    rows.sort_by_key(|r| r.name());
+    match &options.sort_by {
+        SortBy::Name => {}
+        SortBy::Total => rows.sort_by_key(|r| Reverse(r.total_curses())),
+        SortBy::Rate => {
+            rows.sort_by(|a, b| b.curses_per_commit().total_cmp(&a.curses_per_commit()))
+        }
+        SortBy::Percent => {
+            rows.sort_by(|a, b| b.naughty_percentage().total_cmp(&a.naughty_percentage()))
+        }
+        SortBy::Score => rows.sort_by(|a, b| b.score().total_cmp(&a.score())),
+        SortBy::Word(word) => rows.sort_by_key(|r| Reverse(r.curses().get(word).copied())),
+    }
+    if options.reverse {
+        rows.reverse();
+    }
+    if let Some(top) = options.top {
+        rows.truncate(top);
+    }
+
+    rows
+}
+
+/// How a cell in the table is colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shade {
+    /// Not colored at all.
+    Plain,
+    /// A count of curses, shaded by how close it is to the highest count.
+    Count(usize),
+    /// The name of the naughtiest author or team.
+    Naughtiest,
+}
+
+/// A cell in a table.
+#[derive(Debug, Clone)]
+struct Cell {
+    text: String,
+    shade: Shade,
+}
+
+impl Cell {
+    /// A cell with a count of curses.
+    fn count(count: usize) -> Self {
+        Cell {
+            text: count.to_string(),
+            shade: Shade::Count(count),
+        }
+    }
+
+    /// A cell with a name, highlighted if it is the naughtiest.
+    fn name(name: &str, naughtiest: Option<&str>) -> Self {
+        Cell {
+            text: name.to_owned(),
+            shade: if naughtiest == Some(name) {
+                Shade::Naughtiest
+            } else {
+                Shade::Plain
+            },
+        }
+    }
+
+    /// The text of the cell, colored like a heatmap relative to `max`.
+    fn styled(&self, max: usize) -> String {
+        let style = Style::new().force_styling(true);
+        let style = match self.shade {
+            Shade::Plain => return self.text.clone(),
+            Shade::Naughtiest => style.red().bold().underlined(),
+            Shade::Count(0) => style.dim(),
+            Shade::Count(count) if count * 3 <= max => style.yellow(),
+            Shade::Count(count) if count * 3 <= max * 2 => style.color256(208),
+            Shade::Count(_) => style.red().bold(),
+        };
+
+        style.apply_to(&self.text).to_string()
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell {
+            text,
+            shade: Shade::Plain,
+        }
+    }
+}
+
+/// A table with a header, the rows and a footer summing up the rows.
+struct Table {
+    header: Vec<Cell>,
+    rows: Vec<Vec<Cell>>,
+    footer: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    /// Align the columns of the table, separating the header and the footer
+    /// from the rows with dashes (`----`) as wide as the columns.
+    fn render(&self, show_footer: bool, colored: bool) -> Result<String, Box<dyn Error>> {
+        let mut tw = TabWriter::new(vec![]).ansi(true);
+        let separators: Vec<_> = (0..self.header.len())
+            .map(|column| {
+                let width = iter::once(&self.header)
+                    .chain(&self.rows)
+                    .chain(&self.footer)
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.text.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                "-".repeat(width)
+            })
+            .collect();
+        let max = self
+            .rows
+            .iter()
+            .flatten()
+            .filter_map(|cell| match cell.shade {
+                Shade::Count(count) => Some(count),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        let line = |row: &[Cell]| {
+            row.iter()
+                .map(|cell| {
+                    if colored {
+                        cell.styled(max)
+                    } else {
+                        cell.text.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        };
+
+        writeln!(tw, "{}", line(&self.header))?;
+        writeln!(tw, "{}", separators.join("\t"))?;
+        for row in &self.rows {
+            writeln!(tw, "{}", line(row))?;
+        }
+        if show_footer {
+            writeln!(tw, "{}", separators.join("\t"))?;
+            for row in &self.footer {
+                writeln!(tw, "{}", line(row))?;
+            }
+        }
+        tw.flush()?;
+
+        Ok(String::from_utf8(tw.into_inner()?)?)
+    }
+
+    /// Width of the widest line when rendered.
+    fn width(&self) -> usize {
+        self.render(true, false)
+            .map(|table| table.lines().map(|l| l.chars().count()).max().unwrap_or(0))
+            .unwrap_or(0)
+    }
+}
+
+/// Build a table with authors or teams as rows and curses as columns. Any
+/// curses beyond `max_columns` are folded into a single `other` column.
+fn wide_table(
+    repo: &Repo,
+    by: GroupBy,
+    rows: &[&dyn Tally],
+    curses: &[(String, usize)],
+    max_columns: Option<usize>,
+) -> Table {
+    let (shown, folded) = curses.split_at(max_columns.unwrap_or(curses.len()).min(curses.len()));
+    let count = |tally: &dyn Tally, curse: &str| tally.curses().get(curse).copied().unwrap_or(0);
+    let naughtiest = naughtiest(rows);
+    let share = is_split(rows);
+
+    let mut header = vec![by.header().to_owned()];
+    header.extend(shown.iter().map(|(curse, _)| curse.clone()));
+    if !folded.is_empty() {
+        header.push(OTHER.to_owned());
+    }
+    header.push(SUMMARY_COLUMNS[0].to_owned());
+    if share {
+        header.push(SHARE.to_owned());
+    }
+    header.extend(SUMMARY_COLUMNS[1..].iter().map(|c| c.to_string()));
+
+    let row = |tally: &dyn Tally| {
+        let mut out = vec![Cell::name(tally.name(), naughtiest)];
+        out.extend(
+            shown
+                .iter()
+                .map(|(curse, _)| Cell::count(count(tally, curse))),
+        );
+        if !folded.is_empty() {
+            out.push(Cell::count(
+                folded.iter().map(|(curse, _)| count(tally, curse)).sum(),
+            ));
+        }
+        out.push(tally.total_curses().to_string().into());
+        if share {
+            out.push(format_score(tally.score()).into());
+        }
+        out.extend(format_rates(tally).map(Cell::from));
+        out
+    };
+
+    let total = iter::once("Overall".to_owned())
+        .chain(row(repo).into_iter().skip(1).map(|cell| cell.text))
+        .map(Cell::from)
+        .collect();
+
+    Table {
+        header: header.into_iter().map(Cell::from).collect(),
+        rows: rows.iter().map(|r| row(*r)).collect(),
+        footer: vec![total],
+    }
+}
+
+/// Build a table with curses as rows and authors or teams as columns. Any
+/// authors or teams beyond `max_columns` are folded into a single `other`
+/// column.
+fn transposed_table(
+    repo: &Repo,
+    by: GroupBy,
+    rows: &[&dyn Tally],
+    curses: &[(String, usize)],
+    max_columns: Option<usize>,
+) -> Table {
+    let (shown, folded) = rows.split_at(max_columns.unwrap_or(rows.len()).min(rows.len()));
+    let naughtiest = naughtiest(rows);
+    let other = fold(folded);
+    let mut columns = shown.to_vec();
+    if !folded.is_empty() {
+        columns.push(&other);
+    }
+
+    let mut header = vec![Cell::from(format!("Curse / {}", by.header()))];
+    header.extend(
+        columns
+            .iter()
+            .map(|tally| Cell::name(tally.name(), naughtiest)),
+    );
+    header.push(Cell::from("Overall".to_owned()));
+
+    let row = |name: &str, cell: &dyn Fn(&dyn Tally) -> Cell, total: Cell| {
+        let mut out = vec![Cell::from(name.to_owned())];
+        out.extend(columns.iter().map(|tally| cell(*tally)));
+        out.push(total);
+        out
+    };
+
+    let body = curses
+        .iter()
+        .map(|(curse, count)| {
+            row(
+                curse,
+                &|tally| Cell::count(tally.curses().get(curse).copied().unwrap_or(0)),
+                Cell::from(count.to_string()),
+            )
+        })
+        .collect();
+
+    let [rate, percent] = format_rates(repo);
+    let mut footer = vec![row(
+        SUMMARY_COLUMNS[0],
+        &|tally| tally.total_curses().to_string().into(),
+        repo.total_curses().to_string().into(),
+    )];
+    if is_split(rows) {
+        footer.push(row(
+            SHARE,
+            &|tally| format_score(tally.score()).into(),
+            format_score(repo.score()).into(),
+        ));
+    }
+    footer.extend([
+        row(
+            SUMMARY_COLUMNS[1],
+            &|tally| format_rates(tally)[0].clone().into(),
+            rate.into(),
+        ),
+        row(
+            SUMMARY_COLUMNS[2],
+            &|tally| format_rates(tally)[1].clone().into(),
+            percent.into(),
+        ),
+    ]);
+
+    Table {
+        header,
+        rows: body,
+        footer,
+    }
+}
+
+/// Render a summary of the repository as a whole, listing the top curses.
+fn summary(repo: &Repo, options: &TableOptions) -> Result<String, Box<dyn Error>> {
+    let summary = Summary::new(repo, options.top.unwrap_or(TOP_CURSES));
+    let date = |time: Option<i64>| time.map_or_else(|| "-".to_owned(), format_date);
+    let top_curses: Vec<_> = summary
+        .top_curses
+        .iter()
+        .map(|(curse, count)| format!("{} ({})", curse, count))
+        .collect();
+    let rows = [
+        ("Repository", summary.name),
+        (
+            "Commits",
+            format!(
+                "{} ({} naughty)",
+                summary.total_commits, summary.naughty_commits
+            ),
+        ),
+        (
+            "Authors",
+            format!(
+                "{} ({} naughty)",
+                summary.total_authors, summary.naughty_authors
+            ),
+        ),
+        ("Curses", summary.total_curses.to_string()),
+        ("Per commit", format!("{:.2}", summary.curses_per_commit)),
+        ("Top curses", top_curses.join(", ")),
+        ("First commit", date(summary.first_commit)),
+        ("Last commit", date(summary.last_commit)),
+        ("Scanned in", format!("{:.2?}", summary.scan_time)),
+    ];
+
+    let mut tw = TabWriter::new(vec![]);
+    for (label, value) in rows {
+        writeln!(tw, "{}\t{}", label, value)?;
+    }
+    tw.flush()?;
+
+    Ok(String::from_utf8(tw.into_inner()?)?)
+}
+
+/// Render a small table per author with only their own curses, the most
+/// used first, headed by their totals and first and last naughty commits.
+fn author_details(repo: &Repo, options: &TableOptions) -> Result<String, Box<dyn Error>> {
+    let authors: Vec<&dyn Tally> = repo
+        .authors
+        .values()
+        .filter(|author| author.total_curses > 0)
+        .map(|author| author as &dyn Tally)
+        .collect();
+
+    let mut details = Vec::new();
+    for author in sort_rows(authors, options) {
+        let [rate, percent] = format_rates(author);
+        let mut detail = format!(
+            "{}\n{} commits, {} naughty ({}), {} curses, {} per commit\n",
+            author.name(),
+            author.total_commits(),
+            author.naughty_commits(),
+            percent,
+            author.total_curses(),
+            rate
+        );
+
+        // Newest first, keeping the order of the history for commits made at
+        // the same time
+        let mut records: Vec<&CommitRecord> = repo
+            .records
+            .iter()
+            .filter(|record| record.credited.iter().any(|name| name == author.name()))
+            .collect();
+        records.sort_by_key(|record| Reverse(record.time));
+        if let (Some(first), Some(last)) = (records.last(), records.first()) {
+            for (label, record) in [("First", first), ("Last", last)] {
+                detail.push_str(&format!(
+                    "{:<5} {} {} {}\n",
+                    label,
+                    record.short_id(),
+                    record.date(),
+                    record.summary()
+                ));
+            }
+        }
+
+        let mut curses: Vec<(&String, &usize)> = author.curses().iter().collect();
+        curses.sort_by_key(|(curse, count)| (Reverse(**count), *curse));
+        let table = Table {
+            header: vec!["Curse".to_owned().into(), "Count".to_owned().into()],
+            rows: curses
+                .into_iter()
+                .map(|(curse, count)| vec![curse.clone().into(), Cell::count(*count)])
+                .collect(),
+            footer: Vec::new(),
+        };
+        detail.push('\n');
+        detail.push_str(&table.render(false, options.colored)?);
+        details.push(detail);
+    }
+
+    Ok(details.join("\n"))
+}
+
+/// Find the name of the naughtiest author or team, the one with the highest
+/// score.
+fn naughtiest<'a>(rows: &[&'a dyn Tally]) -> Option<&'a str> {
+    rows.iter()
+        .max_by(|a, b| a.score().total_cmp(&b.score()))
+        .map(|tally| tally.name())
+}
+
+/// Fold several authors or teams into a single team.
+fn fold(tallies: &[&dyn Tally]) -> Team {
+    let mut other = Team::new(OTHER);
+    for tally in tallies {
+        other.total_commits += tally.total_commits();
+        other.naughty_commits += tally.naughty_commits();
+        other.total_curses += tally.total_curses();
+        other.score += tally.score();
+        for (curse, count) in tally.curses() {
+            *other.curses.entry(curse.clone()).or_insert(0) += count;
+        }
+    }
+
+    other
+}
+
+/// Build tables of how the summary, the authors and the curses changed
+/// between two repositories or revision ranges.
+fn comparison_tables(comparison: &Comparison) -> [Table; 3] {
+    let header = |first: &str| -> Vec<Cell> {
+        [first, &comparison.before, &comparison.after, "Change"]
+            .iter()
+            .map(|h| Cell::from(h.to_string()))
+            .collect()
+    };
+    let row = |delta: &Delta| -> Vec<Cell> {
+        vec![
+            delta.name.clone().into(),
+            delta.before.to_string().into(),
+            delta.after.to_string().into(),
+            match delta.change() {
+                0 => "0".to_owned().into(),
+                change => format!("{:+}", change).into(),
+            },
+        ]
+    };
+
+    let (before, after) = comparison.curses_per_commit;
+    let summary = Table {
+        header: header(""),
+        rows: vec![
+            row(&comparison.total_commits),
+            row(&comparison.total_curses),
+            vec![
+                "per commit".to_owned().into(),
+                format!("{:.2}", before).into(),
+                format!("{:.2}", after).into(),
+                format!("{:+.2}", after - before).into(),
+            ],
+        ],
+        footer: Vec::new(),
+    };
+    let authors = Table {
+        header: header("Author"),
+        rows: comparison.authors.iter().map(row).collect(),
+        footer: Vec::new(),
+    };
+    let curses = Table {
+        header: header("Curse"),
+        rows: comparison.curses.iter().map(row).collect(),
+        footer: Vec::new(),
+    };
+
+    [summary, authors, curses]
+}
+
+/// Print how many commits by bots were excluded and how many had to be
+/// decoded lossily, if any.
+fn table_notes(repo: &Repo, out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    if repo.lossy_commits > 0 {
+        writeln!(
+            out,
+            "\nReplaced invalid characters in {} commits",
+            repo.lossy_commits
+        )?;
+    }
+    if repo.bots.is_empty() {
+        return Ok(());
+    }
+
+    let mut bots: Vec<_> = repo.bots.keys().map(String::as_str).collect();
+    bots.sort_unstable();
+    writeln!(
+        out,
+        "\nExcluded {} commits by bots: {}",
+        repo.excluded_commits(),
+        bots.join(", ")
+    )?;
+
+    Ok(())
+}
+
+/// Whether curses were split between co-authors, so that some shares differ
+/// from the total curses.
+fn is_split(rows: &[&dyn Tally]) -> bool {
+    rows.iter()
+        .any(|row| row.score() != row.total_curses() as f64)
+}
+
+/// Format a score, only showing decimals when curses have been split.
+fn format_score(score: f64) -> String {
+    if score.fract() == 0.0 {
+        format!("{}", score)
+    } else {
+        format!("{:.2}", score)
+    }
+}
+
+/// Format the curses per commit and percentage of naughty commits.
+fn format_rates(tally: &dyn Tally) -> [String; 2] {
+    [
+        format!("{:.2}", tally.curses_per_commit()),
+        format!("{:.0}%", tally.naughty_percentage()),
+    ]
+}
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+/// Anything that keeps count of the curses in commits, like authors and teams.
+pub trait Tally {
+    /// Name of whoever the curses are counted for.
+    fn name(&self) -> &str;
+    /// Total count of commits.
+    fn total_commits(&self) -> usize;
+    /// Count of commits containing at least one curse.
+    fn naughty_commits(&self) -> usize;
+    /// Total count of curses.
+    fn total_curses(&self) -> usize;
+    /// Share of the curses, see [`Author::score`](crate::author::Author::score).
+    fn score(&self) -> f64;
+    /// HashMap of all the curses used.
+    fn curses(&self) -> &HashMap<String, usize>;
+
+    /// Average number of curses per commit.
+    fn curses_per_commit(&self) -> f64 {
+        ratio(self.total_curses(), self.total_commits())
+    }
+
+    /// Percentage of commits containing at least one curse.
+    fn naughty_percentage(&self) -> f64 {
+        ratio(self.naughty_commits(), self.total_commits()) * 100.0
+    }
+}
+
+/// Divide two counts, treating division by zero as zero.
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{
+    commit::Identity, error::AngerError, filter::Pattern, repo::merge_counts, tally::Tally,
+};
+
+/// Name of the team for everyone not matched by any team.
+pub const UNASSIGNED: &str = "Unassigned";
+
+/// A team of authors and their combined curses.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+pub struct Team {
+    /// Name of the team.
+    pub name: String,
+    /// Names of all the authors in the team.
+    pub members: BTreeSet<String>,
+    /// Total count of commits by the team.
+    pub total_commits: usize,
+    /// Count of commits by the team containing at least one curse.
+    pub naughty_commits: usize,
+    /// Total count of curses used by the team.
+    pub total_curses: usize,
+    /// Share of the curses attributed to the team.
+    pub score: f64,
+    /// HashMap of all the curses the team used.
+    pub curses: HashMap<String, usize>,
+}
+
+impl Team {
+    /// Initialize a new team from a name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Team {
+            name: name.into(),
+            members: BTreeSet::new(),
+            total_commits: 0,
+            naughty_commits: 0,
+            total_curses: 0,
+            score: 0.0,
+            curses: HashMap::new(),
+        }
+    }
+
+    /// Update a previously used curse or add a new one.
+    pub fn update_occurrence(&mut self, curse: &str) {
+        *self.curses.entry(curse.into()).or_insert(0) += 1;
+    }
+
+    /// Adds the members, commits and curses of the same team in another
+    /// repository.
+    pub fn merge(&mut self, other: &Team) {
+        self.members.extend(other.members.iter().cloned());
+        self.total_commits += other.total_commits;
+        self.naughty_commits += other.naughty_commits;
+        self.total_curses += other.total_curses;
+        self.score += other.score;
+        merge_counts(&mut self.curses, &other.curses);
+    }
+}
+
+impl Tally for Team {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn total_commits(&self) -> usize {
+        self.total_commits
+    }
+
+    fn naughty_commits(&self) -> usize {
+        self.naughty_commits
+    }
+
+    fn total_curses(&self) -> usize {
+        self.total_curses
+    }
+
+    fn score(&self) -> f64 {
+        self.score
+    }
+
+    fn curses(&self) -> &HashMap<String, usize> {
+        &self.curses
+    }
+}
+
+/// A mapping of people to teams, read from a config file where every line
+/// looks like `team = pattern, pattern`. Patterns are globs or `/regex/`
+/// matching names or emails, while `@example.com` matches a whole domain.
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// ```text
+/// # Teams at example.com
+/// backend = @backend.example.com, john@example.com
+/// frontend = /^(ola|kari) /
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Teams {
+    teams: Vec<(String, Vec<Pattern>)>,
+}
+
+impl Teams {
+    /// Read the teams from a config file.
+    pub fn from_file(path: &Path) -> Result<Self, AngerError> {
+        let config = fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        let teams = config
+            .parse()
+            .map_err(|e| AngerError::Config(format!("{}: {}", path.display(), e)))?;
+
+        Ok(teams)
+    }
+
+    /// Find the team of a person, the first team with a matching pattern wins.
+    pub fn team_of(&self, identity: &Identity) -> &str {
+        self.teams
+            .iter()
+            .find(|(_, patterns)| patterns.iter().any(|p| p.matches(identity)))
+            .map_or(UNASSIGNED, |(team, _)| team)
+    }
+}
+
+impl FromStr for Teams {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut teams = Vec::new();
+        for (number, line) in s.lines().enumerate().map(|(n, l)| (n + 1, l.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (team, patterns) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `team = pattern`", number))?;
+            let patterns = patterns
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(|p| match p.strip_prefix('@') {
+                    Some(domain) => format!("*@{}", domain).parse(),
+                    None => p.parse(),
+                })
+                .collect::<Result<Vec<Pattern>, _>>()
+                .map_err(|e| format!("line {}: {}", number, e))?;
+
+            teams.push((team.trim().to_owned(), patterns));
+        }
+
+        Ok(Teams { teams })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn identity(name: &str, email: &str) -> Identity {
+        Identity {
+            name: name.into(),
+            email: email.into(),
+        }
+    }
+
+    #[test]
+    fn test_team_of() {
+        let teams: Teams =
+            "# comment\nbackend = @backend.example.com, john@example.com\n\nfrontend = /^ola /"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            teams.team_of(&identity("Kari", "kari@backend.example.com")),
+            "backend"
+        );
+        assert_eq!(
+            teams.team_of(&identity("John Doe", "john@example.com")),
+            "backend"
+        );
+        assert_eq!(
+            teams.team_of(&identity("Ola Nordmann", "ola@example.com")),
+            "frontend"
+        );
+        assert_eq!(
+            teams.team_of(&identity("Sondre", "sondre@example.com")),
+            UNASSIGNED
+        );
+        assert!("backend".parse::<Teams>().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use phf::{Set, phf_set};
+use phf::{Map, Set, phf_map, phf_set};
 
 /// Adapted from https://github.com/coffee-and-fun/google-profanity-words/blob/main/data/en.txt
 pub static CURSES_SET: Set<&'static str> = phf_set! {
@@ -960,3 +960,83 @@ pub static CURSES_SET: Set<&'static str> = phf_set! {
     "yiffy",
     "zoophilia",
 };
+
+/// Curses that are merely rude, every other curse is moderate unless it is
+/// severe.
+pub static MILD_SET: Set<&'static str> = phf_set! {
+    "arse",
+    "bloody",
+    "bollocks",
+    "bugger",
+    "bum",
+    "butt",
+    "crap",
+    "damn",
+    "dammit",
+    "goddamn",
+    "goddamned",
+    "hell",
+    "piss",
+    "pissed",
+    "poop",
+    "suck",
+    "sucks",
+    "turd",
+};
+
+/// Slurs and words for sexual violence.
+pub static SEVERE_SET: Set<&'static str> = phf_set! {
+    "beaner",
+    "chink",
+    "coon",
+    "cunt",
+    "dyke",
+    "fag",
+    "faggot",
+    "gook",
+    "kike",
+    "nigga",
+    "nigger",
+    "paki",
+    "rape",
+    "rapist",
+    "retard",
+    "spic",
+    "tranny",
+};
+
+/// Milder words to replace curses with when sanitizing messages.
+pub static SYNONYMS: Map<&'static str, &'static str> = phf_map! {
+    "arse" => "rear",
+    "ass" => "fool",
+    "asshole" => "jerk",
+    "bastard" => "rascal",
+    "bitch" => "complain",
+    "bitching" => "complaining",
+    "bloody" => "very",
+    "bollocks" => "nonsense",
+    "bugger" => "nuisance",
+    "bullshit" => "nonsense",
+    "crap" => "junk",
+    "damn" => "darn",
+    "dammit" => "darn it",
+    "dick" => "jerk",
+    "dickhead" => "jerk",
+    "fuck" => "heck",
+    "fucked" => "broken",
+    "fucker" => "jerk",
+    "fucking" => "freaking",
+    "goddamn" => "darn",
+    "hell" => "heck",
+    "jackass" => "fool",
+    "motherfucker" => "jerk",
+    "piss" => "annoy",
+    "pissed" => "annoyed",
+    "shit" => "stuff",
+    "shits" => "stuff",
+    "shitty" => "lousy",
+    "suck" => "stink",
+    "sucks" => "stinks",
+    "turd" => "mess",
+    "wanker" => "jerk",
+};
@@ -0,0 +1,75 @@
+// Not every test uses every helper
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use git2::{Oid, Repository, Signature};
+use tempfile::TempDir;
+
+/// A throwaway git repository for tests that need specific commits.
+pub struct Fixture {
+    /// Removes the repository when the fixture is dropped.
+    _dir: TempDir,
+    pub path: PathBuf,
+    pub repo: Repository,
+}
+
+impl Fixture {
+    /// Initialize an empty repository in a temporary directory.
+    pub fn new() -> Self {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_path_buf();
+        let repo = Repository::init(&path).unwrap();
+        Fixture {
+            _dir: dir,
+            path,
+            repo,
+        }
+    }
+
+    /// Initialize an empty bare repository named `name` in a temporary
+    /// directory.
+    pub fn bare(name: &str) -> Self {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(name);
+        let repo = Repository::init_bare(&path).unwrap();
+        Fixture {
+            _dir: dir,
+            path,
+            repo,
+        }
+    }
+
+    /// Path to the working directory, or the bare repository.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Commit an empty tree on top of `HEAD` with the given author.
+    pub fn commit(&self, name: &str, email: &str, message: &str) -> Oid {
+        self.commit_as((name, email), (name, email), message)
+    }
+
+    /// Commit an empty tree on top of `HEAD` with separate author and
+    /// committer, given as names and emails.
+    pub fn commit_as(&self, author: (&str, &str), committer: (&str, &str), message: &str) -> Oid {
+        let author = Signature::now(author.0, author.1).unwrap();
+        let committer = Signature::now(committer.0, committer.1).unwrap();
+        let tree_id = self.repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = self.repo.find_tree(tree_id).unwrap();
+        let parent = self.repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+
+        self.repo
+            .commit(Some("HEAD"), &author, &committer, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// Add another fixture as a submodule at `path` and check it out.
+    pub fn add_submodule(&self, other: &Fixture, path: &str) {
+        let url = other.path().to_str().unwrap();
+        let mut submodule = self.repo.submodule(url, Path::new(path), true).unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+    }
+}
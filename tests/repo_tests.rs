@@ -1,7 +1,24 @@
-use std::path::Path;
+// `test_commit_count` predates this lint
+#![allow(clippy::unnecessary_to_owned)]
 
-use git_anger_management::repo::Repo;
+mod common;
+
+use std::{fs, path::Path};
+
+use common::Fixture;
+use git_anger_management::{
+    compare::{Comparison, Delta},
+    error::AngerError,
+    hook,
+    options::{Attribution, Options, Role},
+    repo::Repo,
+    sanitize::{self, Style},
+    summary::Summary,
+    tally::Tally,
+    team::UNASSIGNED,
+};
 use git2::Repository;
+use tempfile::TempDir;
 
 #[test]
 fn test_commit_count() {
@@ -49,3 +66,520 @@ fn test_curse_total() {
 
     assert_eq!(total, repo.total_curses);
 }
+
+#[test]
+fn test_co_author_attribution() {
+    let fixture = Fixture::new();
+    fixture.commit(
+        "John Doe",
+        "john@example.com",
+        "Fix this damn bug\n\nCo-authored-by: Ola Nordmann <ola@example.com>",
+    );
+
+    let author_only = Repo::new(fixture.path()).unwrap();
+    assert_eq!(author_only.authors.len(), 1);
+
+    let split = Options {
+        attribution: Attribution::Split,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &split).unwrap();
+    assert_eq!(repo.total_curses, 1);
+    assert_eq!(repo.authors.get("Ola Nordmann").unwrap().score, 0.5);
+    assert_eq!(repo.authors.get("John Doe").unwrap().score, 0.5);
+
+    let full = Options {
+        attribution: Attribution::Full,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &full).unwrap();
+    assert_eq!(repo.curses.get("damn").unwrap(), &1);
+    assert_eq!(repo.authors.get("Ola Nordmann").unwrap().total_curses, 1);
+    assert_eq!(repo.authors.get("John Doe").unwrap().score, 1.0);
+}
+
+#[test]
+fn test_committer_attribution() {
+    let fixture = Fixture::new();
+    let id = fixture.commit_as(
+        ("John Doe", "john@example.com"),
+        ("Ola Nordmann", "ola@example.com"),
+        "Apply this shitty patch",
+    );
+
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert!(repo.authors.contains_key("John Doe"));
+
+    let options = Options {
+        attribute_to: Role::Committer,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert!(!repo.authors.contains_key("John Doe"));
+    assert_eq!(repo.authors.get("Ola Nordmann").unwrap().total_curses, 1);
+
+    let record = &repo.records[0];
+    assert_eq!(record.id, id.to_string());
+    assert_eq!(record.author.email, "john@example.com");
+    assert_eq!(record.committer.name, "Ola Nordmann");
+    assert_eq!(record.summary(), "Apply this shitty patch");
+}
+
+#[test]
+fn test_bot_exclusion() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit(
+        "dependabot[bot]",
+        "49699333+dependabot[bot]@users.noreply.github.com",
+        "Bump shitty dependency",
+    );
+    fixture.commit("Jenkins", "jenkins@ci.example.com", "Release crap");
+
+    let options = Options {
+        bots: vec!["*@ci.example.com".parse().unwrap()],
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 1);
+    assert_eq!(repo.authors.len(), 1);
+    assert_eq!(repo.excluded_commits(), 2);
+    assert_eq!(repo.bots.get("dependabot[bot]").unwrap(), &1);
+
+    let options = Options {
+        include_bots: true,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 3);
+    assert!(repo.bots.is_empty());
+}
+
+#[test]
+fn test_author_filters() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@backend.example.com", "Fix damn bug");
+    fixture.commit("Ola Nordmann", "ola@backend.example.com", "Fix shitty test");
+    fixture.commit("Sondre Nilsen", "sondre@frontend.example.com", "Fuck CSS");
+
+    let options = Options {
+        authors: vec!["*@backend.example.com".parse().unwrap()],
+        exclude_authors: vec!["/^ola/".parse().unwrap()],
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 1);
+    assert_eq!(repo.total_curses, 1);
+    assert!(repo.authors.contains_key("John Doe"));
+}
+
+#[test]
+fn test_author_filters_with_co_authors() {
+    let fixture = Fixture::new();
+    fixture.commit(
+        "Ola Nordmann",
+        "ola@example.com",
+        "Fix damn bug\n\nCo-authored-by: John Doe <john@example.com>",
+    );
+
+    // A selected co-author keeps their curses when the author is filtered out
+    let options = Options {
+        attribution: Attribution::Full,
+        authors: vec!["John*".parse().unwrap()],
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.authors.len(), 1);
+    assert_eq!(repo.authors["John Doe"].total_curses, 1);
+    assert_eq!(repo.total_commits, 1);
+
+    // The curses are still split with those who are filtered out
+    let options = Options {
+        attribution: Attribution::Split,
+        ..options
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.authors["John Doe"].score, 0.5);
+
+    let options = Options {
+        attribution: Attribution::Author,
+        ..options
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 0);
+}
+
+#[test]
+fn test_teams() {
+    let fixture = Fixture::new();
+    fixture.commit(
+        "John Doe",
+        "john@backend.example.com",
+        "Fix damn bug\n\nCo-authored-by: Ola Nordmann <ola@backend.example.com>",
+    );
+    fixture.commit("Sondre Nilsen", "sondre@example.com", "Fuck CSS");
+
+    let options = Options {
+        attribution: Attribution::Full,
+        teams: Some("backend = @backend.example.com".parse().unwrap()),
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.teams.len(), 2);
+
+    let backend = repo.teams.get("backend").unwrap();
+    assert_eq!(backend.members.len(), 2);
+    assert_eq!(backend.total_commits, 1);
+    assert_eq!(backend.total_curses, 1);
+
+    let unassigned = repo.teams.get(UNASSIGNED).unwrap();
+    assert!(unassigned.members.contains("Sondre Nilsen"));
+    assert_eq!(unassigned.curses.get("fuck").unwrap(), &1);
+}
+
+#[test]
+fn test_rates() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn shitty bug");
+    fixture.commit("John Doe", "john@example.com", "Add tests");
+
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert_eq!(repo.naughty_commits, 1);
+    assert_eq!(repo.curses_per_commit(), 1.0);
+    assert_eq!(repo.naughty_percentage(), 50.0);
+
+    let john = repo.authors.get("John Doe").unwrap();
+    assert_eq!(john.naughty_commits, 1);
+    assert_eq!(john.naughty_percentage(), 50.0);
+}
+
+#[test]
+fn test_merged_repos() {
+    let first = Fixture::new();
+    first.commit("John Doe", "john@example.com", "Fix damn bug");
+    let second = Fixture::new();
+    second.commit("John Doe", "john@example.com", "Fix another damn bug");
+    second.commit("Ola Nordmann", "ola@example.com", "Shit");
+
+    let repos = [
+        Repo::new(first.path()).unwrap(),
+        Repo::new(second.path()).unwrap(),
+    ];
+    let merged = Repo::merged("all", &repos);
+    assert_eq!(merged.total_commits, 3);
+    assert_eq!(merged.authors.len(), 2);
+    assert_eq!(merged.curses.get("damn").unwrap(), &2);
+    assert_eq!(merged.records.len(), 3);
+
+    let john = merged.authors.get("John Doe").unwrap();
+    assert_eq!(john.total_commits, 2);
+    assert_eq!(john.curses.get("damn").unwrap(), &2);
+}
+
+#[test]
+fn test_find_all_repos() {
+    let root = tempfile::TempDir::new().unwrap();
+    for path in ["one", "nested/two", "one/ignored"] {
+        Repository::init(root.path().join(path)).unwrap();
+    }
+
+    let repos = Repo::find_all(root.path()).unwrap();
+    assert_eq!(
+        repos,
+        vec![root.path().join("nested/two"), root.path().join("one")]
+    );
+}
+
+#[test]
+fn test_compare_ranges() {
+    let fixture = Fixture::new();
+    let first = fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit("John Doe", "john@example.com", "Add tests");
+    fixture.commit("Ola Nordmann", "ola@example.com", "Shit, shit");
+
+    let scan = |range: &str| {
+        let options = Options {
+            range: Some(range.to_owned()),
+            ..Options::default()
+        };
+        Repo::with_options(fixture.path(), &options).unwrap()
+    };
+    let before = scan(&first.to_string());
+    let after = scan(&format!("{}..HEAD", first));
+    assert_eq!(before.total_commits, 1);
+    assert_eq!(after.total_commits, 2);
+
+    let comparison = Comparison::new(&before, &after);
+    assert_eq!(comparison.total_curses.change(), 1);
+    assert_eq!(
+        comparison.authors,
+        vec![
+            Delta::new("John Doe", 1, 0),
+            Delta::new("Ola Nordmann", 0, 2)
+        ]
+    );
+    assert_eq!(comparison.curses_per_commit, (1.0, 1.0));
+}
+
+#[test]
+fn test_submodules() {
+    let sub = Fixture::new();
+    sub.commit("Ola Nordmann", "ola@example.com", "Shitty submodule");
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.add_submodule(&sub, "vendor/sub");
+
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert!(repo.submodules.is_empty());
+
+    let options = Options {
+        submodules: true,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 1);
+    assert_eq!(repo.submodules.len(), 1);
+    assert_eq!(repo.submodules[0].name, "vendor/sub");
+    assert_eq!(repo.submodules[0].total_curses, 1);
+    // Merging counts the submodules as well
+    let merged = Repo::merged("all", [&repo]);
+    assert_eq!(merged.total_commits, 2);
+    assert_eq!(merged.total_curses, 2);
+    assert!(merged.submodules.is_empty());
+
+    let options = Options {
+        submodules: true,
+        fold_submodules: true,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+    assert_eq!(repo.total_commits, 2);
+    assert!(repo.submodules.is_empty());
+    assert!(repo.authors.contains_key("Ola Nordmann"));
+}
+
+#[test]
+fn test_bare_repo() {
+    let fixture = Fixture::bare("project.git");
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert_eq!(repo.name, "project");
+    assert_eq!(repo.total_curses, 1);
+}
+
+#[test]
+fn test_discover_from_subdirectory() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture
+        .repo
+        .remote("origin", "git@github.com:sondr3/git-anger-management.git")
+        .unwrap();
+    let subdirectory = fixture.path().join("src/nested");
+    std::fs::create_dir_all(&subdirectory).unwrap();
+
+    let repo = Repo::new(&subdirectory).unwrap();
+    assert_eq!(repo.name, "git-anger-management");
+    assert_eq!(repo.total_commits, 1);
+}
+
+#[test]
+fn test_worktree() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("feature");
+    fixture.repo.worktree("feature", &path, None).unwrap();
+
+    let repo = Repo::new(&path).unwrap();
+    assert_eq!(repo.name, "feature");
+    assert_eq!(repo.total_curses, 1);
+}
+
+#[test]
+fn test_errors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    assert!(matches!(
+        Repo::new(dir.path()),
+        Err(AngerError::NotARepository(_))
+    ));
+
+    let fixture = Fixture::new();
+    assert!(matches!(
+        Repo::commits(&fixture.repo),
+        Err(AngerError::EmptyRepository(_))
+    ));
+
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    let options = Options {
+        range: Some("v1.0..HEAD".to_owned()),
+        ..Options::default()
+    };
+    assert!(matches!(
+        Repo::with_options(fixture.path(), &options),
+        Err(AngerError::InvalidRevision(range)) if range == "v1.0..HEAD"
+    ));
+}
+
+#[test]
+fn test_empty_repo() {
+    let fixture = Fixture::new();
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert_eq!(repo.total_commits, 0);
+    assert_eq!(repo.total_curses, 0);
+}
+
+#[test]
+fn test_orphan_branch() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.repo.set_head("refs/heads/fresh").unwrap();
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert_eq!(repo.total_commits, 0);
+    assert_eq!(repo.total_curses, 0);
+}
+
+#[test]
+fn test_message_encoding() {
+    let fixture = Fixture::new();
+    let tree = fixture.repo.treebuilder(None).unwrap().write().unwrap();
+    // git2 only creates UTF-8 commits, so write them by hand
+    let mut latin1 = format!("tree {}\n", tree).into_bytes();
+    latin1.extend_from_slice(b"author J\xf8rgen <jorgen@example.com> 0 +0000\n");
+    latin1.extend_from_slice(b"committer J\xf8rgen <jorgen@example.com> 0 +0000\n");
+    latin1.extend_from_slice(b"encoding ISO-8859-1\n\nFix damn bug in f\xf8rste\n");
+    let odb = fixture.repo.odb().unwrap();
+    let parent = odb.write(git2::ObjectType::Commit, &latin1).unwrap();
+
+    let mut broken = format!("tree {}\nparent {}\n", tree, parent).into_bytes();
+    broken.extend_from_slice(b"author John Doe <john@example.com> 1 +0000\n");
+    broken.extend_from_slice(b"committer John Doe <john@example.com> 1 +0000\n");
+    broken.extend_from_slice(b"\nFix \xff shit\n");
+    let head = odb.write(git2::ObjectType::Commit, &broken).unwrap();
+    fixture
+        .repo
+        .reference("HEAD", head, true, "hand written commits")
+        .unwrap();
+
+    let repo = Repo::new(fixture.path()).unwrap();
+    assert_eq!(repo.total_commits, 2);
+    assert_eq!(repo.total_curses, 2);
+    assert_eq!(repo.lossy_commits, 1);
+    assert!(repo.authors.contains_key("J\u{f8}rgen"));
+    assert_eq!(repo.records[0].message, "Fix \u{fffd} shit\n");
+    assert_eq!(repo.records[1].message, "Fix damn bug in f\u{f8}rste\n");
+}
+
+#[test]
+fn test_summary() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit("John Doe", "john@example.com", "Remove shit, damn it");
+    fixture.commit("Ola Nordmann", "ola@example.com", "Add crap");
+    fixture.commit("Kari Nordmann", "kari@example.com", "Add tests");
+    let repo = Repo::new(fixture.path()).unwrap();
+
+    let summary = Summary::new(&repo, 2);
+    assert_eq!(summary.total_commits, 4);
+    assert_eq!(summary.naughty_commits, 3);
+    assert_eq!(summary.total_authors, 3);
+    assert_eq!(summary.naughty_authors, 2);
+    assert_eq!(summary.total_curses, 4);
+    assert_eq!(summary.curses_per_commit, 1.0);
+    assert_eq!(
+        summary.top_curses,
+        [("damn".to_owned(), 2), ("crap".to_owned(), 1)]
+    );
+    assert!(summary.first_commit.is_some());
+    assert!(summary.first_commit <= summary.last_commit);
+}
+
+#[test]
+fn test_from_text() {
+    let text = "Fix damn bug\n\nRemove shit,\ndamn it\n  \n\nAdd tests\n";
+    let repo = Repo::from_text("stdin", text);
+    assert_eq!(repo.name, "stdin");
+    assert_eq!(repo.total_commits, 3);
+    assert_eq!(repo.naughty_commits, 2);
+    assert_eq!(repo.total_curses, 3);
+    assert_eq!(repo.curses["damn"], 2);
+    assert_eq!(repo.curses["shit"], 1);
+    assert!(repo.records.is_empty());
+
+    let author = &repo.authors["stdin"];
+    assert_eq!(author.total_commits, 3);
+    assert_eq!(author.total_curses, 3);
+}
+
+#[test]
+fn test_from_nul_separated_text() {
+    // Like `git log -z --format=%B`, where bodies are separated by blank lines
+    let text = "Fix damn bug\n\nIt was a shit bug.\n\0Add tests\n\0";
+    let repo = Repo::from_text("stdin", text);
+    assert_eq!(repo.total_commits, 2);
+    assert_eq!(repo.naughty_commits, 1);
+    assert_eq!(repo.total_curses, 2);
+}
+
+#[test]
+fn test_install_hook() {
+    let fixture = Fixture::new();
+    let path = hook::install(&fixture.path, &["--strict"], false).unwrap();
+    assert_eq!(path, fixture.repo.path().join("hooks").join("commit-msg"));
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        hook::script(&["--strict"])
+    );
+
+    // Our own hook is replaced, anyone else's only when forced
+    hook::install(&fixture.path, &[], false).unwrap();
+    fs::write(&path, "#!/bin/sh\n").unwrap();
+    assert!(matches!(
+        hook::install(&fixture.path, &[], false),
+        Err(AngerError::Io(_))
+    ));
+    hook::install(&fixture.path, &[], true).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), hook::script(&[]));
+
+    // Git only runs the hooks of the main repository in linked worktrees
+    fixture.commit("John Doe", "john@example.com", "Add tests");
+    let dir = TempDir::new().unwrap();
+    let worktree = dir.path().join("wt");
+    fixture.repo.worktree("wt", &worktree, None).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(hook::install(&worktree, &[], false).unwrap(), path);
+    assert!(path.exists());
+
+    fixture
+        .repo
+        .config()
+        .unwrap()
+        .set_str("core.hooksPath", ".githooks")
+        .unwrap();
+    let path = hook::install(&fixture.path, &[], false).unwrap();
+    assert_eq!(path, fixture.path.join(".githooks").join("commit-msg"));
+}
+
+#[test]
+fn test_sanitize_every_commit() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit(
+        "dependabot[bot]",
+        "49699333+dependabot[bot]@users.noreply.github.com",
+        "Bump damn dep",
+    );
+
+    let options = Options {
+        exclude_authors: vec!["John*".parse().unwrap()],
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &sanitize::all_commits(&options)).unwrap();
+    let mut sanitized: Vec<_> = sanitize::rewrites(&repo.records, Style::Mask)
+        .into_iter()
+        .map(|rewrite| rewrite.sanitized)
+        .collect();
+    sanitized.sort();
+    assert_eq!(sanitized, ["Bump d*** dep", "Fix d*** bug"]);
+}
@@ -0,0 +1,495 @@
+mod common;
+
+use common::Fixture;
+use git_anger_management::{
+    compare::Comparison,
+    json::JsonReporter,
+    options::{Attribution, GroupBy, Options},
+    repo::Repo,
+    report::Reporter,
+    table::{Layout, SortBy, TableOptions, TableReporter},
+};
+
+fn table_reporter(layout: Layout) -> TableReporter {
+    TableReporter {
+        options: TableOptions {
+            by: GroupBy::Author,
+            sort_by: SortBy::Name,
+            reverse: false,
+            top: None,
+            layout,
+            max_columns: None,
+            colored: false,
+            width: None,
+            author_detail: false,
+            summary: false,
+        },
+    }
+}
+
+fn scan() -> Repo {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit("Ola Nordmann", "ola@example.com", "Remove shit, damn it");
+    Repo::new(fixture.path()).unwrap()
+}
+
+/// Four authors cursing more and more, with a curse of their own each.
+fn crew() -> Repo {
+    let fixture = Fixture::new();
+    fixture.commit("Anna", "anna@example.com", "Fix damn bug");
+    fixture.commit("Bob", "bob@example.com", "Remove shit, shit");
+    fixture.commit("Carl", "carl@example.com", "Hell, hell, damn");
+    fixture.commit("Dana", "dana@example.com", "Crap, crap, crap, crap");
+    Repo::new(fixture.path()).unwrap()
+}
+
+fn render(reporter: &TableReporter, repo: &Repo) -> String {
+    let mut out = Vec::new();
+    reporter.render(repo, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn cells(line: &str) -> Vec<&str> {
+    line.split_whitespace().collect()
+}
+
+fn width(table: &str) -> usize {
+    table
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap()
+}
+
+#[test]
+fn test_table_reporter() {
+    let repo = scan();
+    let mut out = Vec::new();
+    table_reporter(Layout::Wide)
+        .render(&repo, &mut out)
+        .unwrap();
+    let table = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = table.lines().collect();
+
+    assert!(lines[0].starts_with("Author"));
+    assert!(lines[2].starts_with("John Doe"));
+    assert!(lines[3].starts_with("Ola Nordmann"));
+    assert!(lines[5].starts_with("Overall"));
+}
+
+#[test]
+fn test_json_reporter() {
+    let repo = scan();
+    let mut out = Vec::new();
+    JsonReporter::default().render(&repo, &mut out).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+    assert_eq!(json["total_commits"], 2);
+    assert_eq!(json["curses"]["damn"], 2);
+    assert_eq!(json["authors"]["John Doe"]["total_curses"], 1);
+    // Messages and emails of commits are only part of the log
+    assert!(json.get("records").is_none());
+
+    let reporter = JsonReporter {
+        summary: true,
+        top: Some(1),
+        ..JsonReporter::default()
+    };
+    let mut out = Vec::new();
+    reporter.render(&repo, &mut out).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["top_curses"], serde_json::json!([["damn", 2]]));
+}
+
+#[test]
+fn test_render_all() {
+    let repo = scan();
+    let merged = Repo::merged("All repositories", [&repo, &repo]);
+    let mut out = Vec::new();
+    JsonReporter::default()
+        .render_all(&[&repo, &repo], Some(&merged), &mut out)
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json["repositories"].as_array().unwrap().len(), 2);
+    assert_eq!(json["merged"]["total_curses"], 6);
+
+    let mut out = Vec::new();
+    table_reporter(Layout::Transposed)
+        .render_all(&[&repo], Some(&merged), &mut out)
+        .unwrap();
+    let tables = String::from_utf8(out).unwrap();
+    assert!(tables.contains(&format!("{}\n\nCurse / Author", repo.name)));
+    assert!(tables.contains("All repositories\n\nCurse / Author"));
+}
+
+#[test]
+fn test_render_comparison() {
+    let repo = scan();
+    let comparison = Comparison::new(&Repo::empty("before"), &repo);
+    let mut out = Vec::new();
+    table_reporter(Layout::Auto)
+        .render_comparison(&comparison, &mut out)
+        .unwrap();
+    let tables = String::from_utf8(out).unwrap();
+    assert!(
+        tables
+            .lines()
+            .any(|line| line.split_whitespace().eq(["curses", "0", "3", "+3"]))
+    );
+}
+
+#[test]
+fn test_author_detail() {
+    let fixture = Fixture::new();
+    fixture.commit("John Doe", "john@example.com", "Fix damn bug");
+    fixture.commit("John Doe", "john@example.com", "Remove shit, damn it");
+    fixture.commit("Ola Nordmann", "ola@example.com", "Add tests");
+    let repo = Repo::new(fixture.path()).unwrap();
+
+    let mut reporter = table_reporter(Layout::Wide);
+    reporter.options.author_detail = true;
+    let mut out = Vec::new();
+    reporter.render(&repo, &mut out).unwrap();
+    let details = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = details.lines().collect();
+
+    assert_eq!(lines[0], "John Doe");
+    assert_eq!(
+        lines[1],
+        "2 commits, 2 naughty (100%), 3 curses, 1.50 per commit"
+    );
+    assert!(lines[2].starts_with("First") && lines[2].ends_with("Fix damn bug"));
+    assert!(lines[3].starts_with("Last") && lines[3].ends_with("Remove shit, damn it"));
+    assert_eq!(
+        lines[7].split_whitespace().collect::<Vec<_>>(),
+        ["damn", "2"]
+    );
+    assert_eq!(
+        lines[8].split_whitespace().collect::<Vec<_>>(),
+        ["shit", "1"]
+    );
+    assert!(!details.contains("Ola Nordmann"));
+}
+
+#[test]
+fn test_render_log() {
+    let repo = scan();
+    let mut out = Vec::new();
+    table_reporter(Layout::Auto)
+        .render_log(&repo.records, &mut out)
+        .unwrap();
+    let log = String::from_utf8(out).unwrap();
+    let lines: Vec<_> = log.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[2].contains("Ola Nordmann") && lines[2].ends_with("Remove shit, damn it"));
+    assert!(lines[3].contains("John Doe") && lines[3].ends_with("Fix damn bug"));
+
+    let mut out = Vec::new();
+    JsonReporter::default()
+        .render_log(&repo.records, &mut out)
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(json[0]["curses"]["shit"], 1);
+}
+
+#[test]
+fn test_split_attribution() {
+    let fixture = Fixture::new();
+    fixture.commit(
+        "John Doe",
+        "john@example.com",
+        "Fucking hell\n\nCo-authored-by: Ola Nordmann <ola@example.com>",
+    );
+    fixture.commit("Ola Nordmann", "ola@example.com", "Damn it");
+    let options = Options {
+        attribution: Attribution::Split,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+
+    let render = |layout| {
+        let mut out = Vec::new();
+        table_reporter(layout).render(&repo, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    let cells = |line: &str| {
+        line.split_whitespace()
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+
+    // Counts and rates use every curse, the share is what is left after splitting
+    let wide = render(Layout::Wide);
+    let lines: Vec<_> = wide.lines().collect();
+    assert_eq!(
+        cells(lines[0]),
+        [
+            "Author", "damn", "fucking", "hell", "Total", "Share", "Per", "commit", "%", "naughty"
+        ]
+    );
+    assert_eq!(
+        cells(lines[2]),
+        ["John", "Doe", "0", "1", "1", "2", "1", "2.00", "100%"]
+    );
+    assert_eq!(
+        cells(lines[3]),
+        ["Ola", "Nordmann", "1", "1", "1", "3", "2", "1.50", "100%"]
+    );
+    assert_eq!(
+        cells(lines[5]),
+        ["Overall", "1", "1", "1", "3", "3", "1.50", "100%"]
+    );
+
+    let transposed = render(Layout::Transposed);
+    let lines: Vec<_> = transposed.lines().collect();
+    assert_eq!(cells(lines[6]), ["Total", "2", "3", "3"]);
+    assert_eq!(cells(lines[7]), ["Share", "1", "2", "3"]);
+
+    // Without splitting shares are the same as the totals
+    let repo = Repo::new(fixture.path()).unwrap();
+    let mut out = Vec::new();
+    table_reporter(Layout::Wide)
+        .render(&repo, &mut out)
+        .unwrap();
+    assert!(!String::from_utf8(out).unwrap().contains("Share"));
+}
+
+#[test]
+fn test_transposed_table() {
+    let repo = crew();
+    let table = render(&table_reporter(Layout::Transposed), &repo);
+    let lines: Vec<_> = table.lines().collect();
+
+    assert_eq!(
+        cells(lines[0]),
+        [
+            "Curse", "/", "Author", "Anna", "Bob", "Carl", "Dana", "Overall"
+        ]
+    );
+    assert_eq!(cells(lines[2]), ["crap", "0", "0", "0", "4", "4"]);
+    assert_eq!(cells(lines[3]), ["damn", "1", "0", "1", "0", "2"]);
+    assert_eq!(cells(lines[7]), ["Total", "1", "2", "3", "4", "10"]);
+}
+
+#[test]
+fn test_max_columns() {
+    let repo = crew();
+    let mut reporter = table_reporter(Layout::Wide);
+    reporter.options.max_columns = Some(2);
+    let table = render(&reporter, &repo);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(
+        cells(lines[0]),
+        [
+            "Author", "crap", "damn", "other", "Total", "Per", "commit", "%", "naughty"
+        ]
+    );
+    assert_eq!(cells(lines[3]), ["Bob", "0", "0", "2", "2", "2.00", "100%"]);
+
+    reporter.options.layout = Layout::Transposed;
+    let table = render(&reporter, &repo);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(
+        cells(lines[0]),
+        ["Curse", "/", "Author", "Anna", "Bob", "other", "Overall"]
+    );
+    assert_eq!(cells(lines[2]), ["crap", "0", "0", "4", "4"]);
+    assert_eq!(cells(lines[7]), ["Total", "1", "2", "7", "10"]);
+}
+
+#[test]
+fn test_auto_layout() {
+    let repo = crew();
+    let wide = render(&table_reporter(Layout::Wide), &repo);
+    let transposed = render(&table_reporter(Layout::Transposed), &repo);
+    assert!(width(&transposed) < width(&wide));
+
+    let auto = |width| {
+        let mut reporter = table_reporter(Layout::Auto);
+        reporter.options.width = width;
+        render(&reporter, &repo)
+    };
+    // Without a width the table is always wide
+    assert_eq!(auto(None), wide);
+    assert_eq!(auto(Some(width(&wide))), wide);
+    assert_eq!(auto(Some(width(&wide) - 1)), transposed);
+
+    // Authors are folded until the table fits, down to a single one
+    let folded = auto(Some(width(&transposed) - 1));
+    assert!(width(&folded) < width(&transposed));
+    assert!(cells(folded.lines().next().unwrap()).contains(&"other"));
+    let narrow = auto(Some(10));
+    assert_eq!(
+        cells(narrow.lines().next().unwrap()),
+        ["Curse", "/", "Author", "Anna", "other", "Overall"]
+    );
+}
+
+#[test]
+fn test_sort() {
+    let fixture = Fixture::new();
+    fixture.commit("Anna", "anna@example.com", "hell hell hell hell hell");
+    for message in ["Add tests", "Add docs", "Fix typo", "Bump version"] {
+        fixture.commit("Anna", "anna@example.com", message);
+    }
+    fixture.commit("Bob", "bob@example.com", "shit shit shit shit shit shit");
+    fixture.commit("Bob", "bob@example.com", "crap crap crap");
+    fixture.commit("Bob", "bob@example.com", "Add tests");
+    fixture.commit("Carl", "carl@example.com", "damn damn");
+    let repo = Repo::new(fixture.path()).unwrap();
+
+    // The names of the rows and curse columns
+    let order = |sort_by: &str, reverse: bool, top: Option<usize>| {
+        let mut reporter = table_reporter(Layout::Wide);
+        reporter.options.sort_by = sort_by.parse().unwrap();
+        reporter.options.reverse = reverse;
+        reporter.options.top = top;
+        let table = render(&reporter, &repo);
+        let lines: Vec<_> = table.lines().collect();
+        let columns: Vec<_> = cells(lines[0])
+            .into_iter()
+            .skip(1)
+            .take_while(|&cell| cell != "Total")
+            .map(str::to_owned)
+            .collect();
+        let rows: Vec<_> = lines[2..]
+            .iter()
+            .take_while(|line| !line.starts_with('-'))
+            .map(|line| cells(line)[0].to_owned())
+            .collect();
+        (rows, columns)
+    };
+
+    let check = |sort_by, reverse, top, rows: &[&str], columns: &[&str]| {
+        let (actual_rows, actual_columns) = order(sort_by, reverse, top);
+        assert_eq!(actual_rows, rows);
+        assert_eq!(actual_columns, columns);
+    };
+
+    check(
+        "name",
+        false,
+        None,
+        &["Anna", "Bob", "Carl"],
+        &["crap", "damn", "hell", "shit"],
+    );
+    // Rows by their own totals and rates, columns by how often each curse is used
+    check(
+        "total",
+        false,
+        None,
+        &["Bob", "Anna", "Carl"],
+        &["shit", "hell", "crap", "damn"],
+    );
+    check(
+        "rate",
+        false,
+        None,
+        &["Bob", "Carl", "Anna"],
+        &["shit", "hell", "crap", "damn"],
+    );
+    check(
+        "percent",
+        false,
+        None,
+        &["Carl", "Bob", "Anna"],
+        &["shit", "hell", "crap", "damn"],
+    );
+    check(
+        "DAMN",
+        false,
+        None,
+        &["Carl", "Anna", "Bob"],
+        &["damn", "shit", "hell", "crap"],
+    );
+    check(
+        "total",
+        true,
+        None,
+        &["Carl", "Anna", "Bob"],
+        &["damn", "crap", "hell", "shit"],
+    );
+    check("total", false, Some(2), &["Bob", "Anna"], &["shit", "hell"]);
+    check("name", true, Some(1), &["Carl"], &["shit"]);
+}
+
+#[test]
+fn test_sort_by_score() {
+    let fixture = Fixture::new();
+    fixture.commit("Anna", "anna@example.com", "damn damn");
+    fixture.commit(
+        "Bob",
+        "bob@example.com",
+        "shit shit shit\n\nCo-authored-by: Carl <carl@example.com>",
+    );
+    let options = Options {
+        attribution: Attribution::Split,
+        ..Options::default()
+    };
+    let repo = Repo::with_options(fixture.path(), &options).unwrap();
+
+    let rows = |sort_by: &str| {
+        let mut reporter = table_reporter(Layout::Wide);
+        reporter.options.sort_by = sort_by.parse().unwrap();
+        render(&reporter, &repo)
+            .lines()
+            .skip(2)
+            .take(3)
+            .map(|line| cells(line)[0].to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(rows("total"), ["Bob", "Carl", "Anna"]);
+    assert_eq!(rows("score"), ["Anna", "Bob", "Carl"]);
+}
+
+#[test]
+fn test_colored_table() {
+    let repo = crew();
+    let mut reporter = table_reporter(Layout::Wide);
+    reporter.options.colored = true;
+    let table = render(&reporter, &repo);
+    let lines: Vec<_> = table.lines().collect();
+
+    let dim = "\x1b[2m0\x1b[0m";
+    let yellow = "\x1b[33m1\x1b[0m";
+    let orange = "\x1b[38;5;208m2\x1b[0m";
+    let red = "\x1b[31m\x1b[1m4\x1b[0m";
+    // Counts are shaded relative to the highest count of 4, only the
+    // naughtiest name is highlighted, and the totals are left alone
+    assert_eq!(
+        lines[0],
+        render(&table_reporter(Layout::Wide), &repo)
+            .lines()
+            .next()
+            .unwrap()
+    );
+    assert_eq!(
+        cells(lines[2]),
+        ["Anna", dim, yellow, dim, dim, "1", "1.00", "100%"]
+    );
+    assert_eq!(
+        cells(lines[4]),
+        ["Carl", dim, yellow, orange, dim, "3", "3.00", "100%"]
+    );
+    assert_eq!(
+        cells(lines[5]),
+        [
+            "\x1b[31m\x1b[1m\x1b[4mDana\x1b[0m",
+            red,
+            dim,
+            dim,
+            dim,
+            "4",
+            "4.00",
+            "100%"
+        ]
+    );
+    assert_eq!(
+        cells(lines[7]),
+        ["Overall", "4", "2", "2", "2", "10", "2.50", "100%"]
+    );
+
+    reporter.options.colored = false;
+    assert!(!render(&reporter, &repo).contains('\x1b'));
+}